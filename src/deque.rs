@@ -1,9 +1,18 @@
-use crate::iterators::{DrainBack, DrainFront, IterBack, IterFront};
+use crate::entry::{Entry, VacantEntry};
+use crate::error::DequeError;
+use crate::iterators::{
+    Chunks, DrainBack, DrainBackN, DrainBackWhile, DrainFront, DrainFrontN, DrainFrontWhile,
+    DrainRange, IterBack, IterCycle, IterFront, IterPositioned,
+};
+use crate::layout::DebugLayout;
+use crate::parts::DequeParts;
+use crate::prune::PruneAction;
 use crate::slot::Slot;
 use crate::token::Token;
+use std::cmp::Ordering;
+use std::collections::{HashMap, TryReserveError};
 use std::fmt;
 use std::iter::FromIterator;
-use std::usize;
 
 /// A deque that supports removing of nodes not in front or back
 /// position, but also nodes in front and back position.
@@ -11,6 +20,12 @@ pub struct Deque<T> {
     // Index of the first element on the free list. MAX when the
     // free-list is empty.
     free_list: usize,
+    // Index of the last element on the free list. MAX when the
+    // free-list is empty.
+    free_tail: usize,
+    // When true, freed slots are added to the tail of the free list
+    // instead of the head, so the oldest freed slot is reused first.
+    fifo: bool,
     // The index of the front of the deque. MAX when the deque is empty.
     pub(crate) front: usize,
     // The index of the back of the deque. MAX when the deque is empty.
@@ -34,10 +49,44 @@ where
     }
 }
 
+impl<T> Clone for Deque<T>
+where
+    T: Clone,
+{
+    fn clone(&self) -> Self {
+        Self {
+            free_list: self.free_list,
+            free_tail: self.free_tail,
+            fifo: self.fifo,
+            front: self.front,
+            back: self.back,
+            next_generation: self.next_generation,
+            len_used: self.len_used,
+            len_free: self.len_free,
+            slots: self.slots.clone(),
+        }
+    }
+
+    // Reuses the existing allocation of `self.slots` where possible.
+    fn clone_from(&mut self, source: &Self) {
+        self.free_list = source.free_list;
+        self.free_tail = source.free_tail;
+        self.fifo = source.fifo;
+        self.front = source.front;
+        self.back = source.back;
+        self.next_generation = source.next_generation;
+        self.len_used = source.len_used;
+        self.len_free = source.len_free;
+        self.slots.clone_from(&source.slots);
+    }
+}
+
 impl<T> Default for Deque<T> {
     fn default() -> Self {
         Self {
             free_list: usize::MAX,
+            free_tail: usize::MAX,
+            fifo: false,
             front: usize::MAX,
             back: usize::MAX,
             next_generation: 0,
@@ -63,8 +112,34 @@ impl<T> Deque<T> {
         Default::default()
     }
 
+    /// Creates an empty `Deque` whose free list is first-in,
+    /// first-out: the slot that was freed longest ago is the next one
+    /// to be reused. By default, the free list is last-in, first-out,
+    /// and the most recently freed slot is reused first.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use token_deque::Deque;
+    ///
+    /// let mut d: Deque<u8> = Deque::new_fifo();
+    /// d.push_back(1);
+    /// d.push_back(2);
+    /// d.pop_front();
+    /// d.pop_front();
+    ///
+    /// assert_eq!(vec![0, 1], d.freelist_order());
+    /// ```
+    pub fn new_fifo() -> Deque<T> {
+        Deque {
+            fifo: true,
+            ..Default::default()
+        }
+    }
+
     /// Create a new `Deque` instance with a freelist at least
-    /// `capacity` elements deep.
+    /// `capacity` elements deep. The free slots are used from the
+    /// highest index down, so index 0 is the last to be allocated.
     ///
     /// # Examples
     ///
@@ -84,6 +159,8 @@ impl<T> Deque<T> {
 
         Deque {
             free_list: next,
+            free_tail: if capacity > 0 { 0 } else { usize::MAX },
+            fifo: false,
             front: usize::MAX,
             back: usize::MAX,
             next_generation: 0,
@@ -93,6 +170,47 @@ impl<T> Deque<T> {
         }
     }
 
+    /// Create a new `Deque` instance with a freelist exactly
+    /// `capacity` elements deep, and an underlying `Vec` with room for
+    /// exactly `capacity` elements.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use token_deque::Deque;
+    ///
+    /// let deque: Deque<u32> = Deque::with_capacity_exact(13);
+    /// assert_eq!(13, deque.capacity());
+    /// assert_eq!(13, deque.len_freelist());
+    /// ```
+    pub fn with_capacity_exact(capacity: usize) -> Deque<T> {
+        let mut l = Self::new();
+        l.slots.reserve_exact(capacity);
+        l.push_free_slots(capacity);
+        l
+    }
+
+    /// Create a new `Deque` by pushing each item of `iter` onto the
+    /// front. This builds the deque in the reverse order of the
+    /// `FromIterator` implementation, which pushes each item onto the
+    /// back.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use token_deque::Deque;
+    ///
+    /// let deque = Deque::from_iter_front(0..3);
+    /// assert_eq!(vec![&2, &1, &0], deque.iter_front().collect::<Vec<_>>());
+    /// ```
+    pub fn from_iter_front<I: IntoIterator<Item = T>>(iter: I) -> Deque<T> {
+        let mut l = Self::new();
+        for i in iter {
+            l.push_front(i);
+        }
+        l
+    }
+
     /// Reserves capacity for at least `additional` more elements to
     /// be inserted into the given `Deque`. Note: this only expands
     /// the size of the underlying `Vec`. It does not add the reserved
@@ -110,6 +228,166 @@ impl<T> Deque<T> {
         self.slots.reserve(additional)
     }
 
+    /// Tries to reserve capacity for at least `additional` more
+    /// elements to be inserted into the given `Deque`. Like
+    /// [`reserve`], this only expands the size of the underlying
+    /// `Vec`. If the allocation fails, or the capacity would overflow,
+    /// an error is returned and the deque is left unchanged.
+    ///
+    /// [`reserve`]: #method.reserve
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use token_deque::Deque;
+    ///
+    /// let mut l: Deque<u32> = Deque::new();
+    /// assert!(l.try_reserve(16).is_ok());
+    /// assert!(l.try_reserve(usize::MAX).is_err());
+    /// ```
+    pub fn try_reserve(&mut self, additional: usize) -> Result<(), TryReserveError> {
+        self.slots.try_reserve(additional)
+    }
+
+    /// Tries to add `additional` slots to the free list, allocating
+    /// space for them in the underlying `Vec`. If the allocation
+    /// fails, or the capacity would overflow, an error is returned and
+    /// the deque is left unchanged.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use token_deque::Deque;
+    ///
+    /// let mut l: Deque<u32> = Deque::new();
+    /// assert!(l.try_reserve_freelist(16).is_ok());
+    /// assert_eq!(16, l.len_freelist());
+    /// ```
+    pub fn try_reserve_freelist(&mut self, additional: usize) -> Result<(), TryReserveError> {
+        self.slots.try_reserve(additional)?;
+        self.push_free_slots(additional);
+
+        Ok(())
+    }
+
+    /// Add exactly `additional` slots to the free list, growing the
+    /// underlying `Vec` with `Vec::reserve_exact` so that no extra
+    /// room is allocated beyond them.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use token_deque::Deque;
+    ///
+    /// let mut l: Deque<u32> = Deque::new();
+    /// l.reserve_freelist_exact(10);
+    /// assert_eq!(10, l.len_freelist());
+    /// assert_eq!(10, l.capacity());
+    /// ```
+    pub fn reserve_freelist_exact(&mut self, additional: usize) {
+        self.slots.reserve_exact(additional);
+        self.push_free_slots(additional);
+    }
+
+    /// Shrinks the capacity of the deque as much as possible, but not
+    /// below `min_capacity`. Free slots at the end of the underlying
+    /// `Vec` are removed from the free list and released first, so
+    /// the free list may shrink as well. Items that are still in the
+    /// deque keep their slots, so their tokens remain valid.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use token_deque::Deque;
+    ///
+    /// let mut l: Deque<u32> = Deque::new();
+    /// let tok = l.push_back(1);
+    /// for i in 0..63 {
+    ///     l.push_back(i);
+    /// }
+    /// for _ in 0..63 {
+    ///     l.pop_back();
+    /// }
+    ///
+    /// l.shrink_to(16);
+    /// assert_eq!(16, l.capacity());
+    /// assert_eq!(Some(&1), l.get(&tok));
+    /// ```
+    pub fn shrink_to(&mut self, min_capacity: usize) {
+        let mut len = self.slots.len();
+        while len > min_capacity && self.slots[len - 1].get_free().is_some() {
+            len -= 1;
+        }
+
+        if len < self.slots.len() {
+            // Relink the free list without the slots being released,
+            // keeping the order of the slots that remain.
+            let mut kept = Vec::new();
+            let mut ix = self.free_list;
+            while usize::MAX != ix {
+                let next = self.slots[ix]
+                    .get_free()
+                    .expect("self.slots[ix] is expected to be free")
+                    .next();
+                if ix < len {
+                    kept.push(ix);
+                }
+                ix = next;
+            }
+
+            let mut next = usize::MAX;
+            for &ix in kept.iter().rev() {
+                self.slots[ix] = Slot::new_free(next);
+                next = ix;
+            }
+
+            self.free_list = next;
+            self.free_tail = kept.last().copied().unwrap_or(usize::MAX);
+            self.len_free = kept.len();
+            self.slots.truncate(len);
+        }
+
+        self.slots.shrink_to(min_capacity);
+    }
+
+    /// The indices of the free slots, in the order they will be reused
+    /// by new items. With the default LIFO free list, a freed slot goes
+    /// to the start of this order; with a deque made by [`new_fifo`], it
+    /// goes to the end. The order only changes when slots are freed,
+    /// allocated, reserved with [`try_reserve_freelist`], or released
+    /// with [`shrink_to`], so the same sequence of operations always
+    /// produces the same layout.
+    ///
+    /// [`new_fifo`]: #method.new_fifo
+    /// [`try_reserve_freelist`]: #method.try_reserve_freelist
+    /// [`shrink_to`]: #method.shrink_to
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use token_deque::Deque;
+    ///
+    /// let mut d: Deque<u8> = Deque::new();
+    /// d.push_back(1);
+    /// d.push_back(2);
+    /// d.pop_front();
+    /// d.pop_front();
+    ///
+    /// assert_eq!(vec![1, 0], d.freelist_order());
+    /// ```
+    pub fn freelist_order(&self) -> Vec<usize> {
+        let mut order = Vec::with_capacity(self.len_free);
+        let mut ix = self.free_list;
+        while usize::MAX != ix {
+            order.push(ix);
+            ix = self.slots[ix]
+                .get_free()
+                .expect("self.slots[ix] is expected to be free")
+                .next();
+        }
+        order
+    }
+
     /// Returns how many items could be held without resizing the
     /// internal vector. Note: this is not necesarily `len() + len_freelist()`.
     ///
@@ -125,6 +403,24 @@ impl<T> Deque<T> {
         self.slots.capacity()
     }
 
+    /// Returns how many more items can be pushed before the internal
+    /// vector is resized. This counts the slots on the free list, which
+    /// are reused first, and the room left at the end of the `Vec`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use token_deque::Deque;
+    ///
+    /// let mut d: Deque<u8> = Deque::with_capacity(16);
+    /// d.push_back(1);
+    ///
+    /// assert_eq!(15, d.capacity_remaining());
+    /// ```
+    pub fn capacity_remaining(&self) -> usize {
+        self.len_free + (self.slots.capacity() - self.slots.len())
+    }
+
     /// The number of items in the deque.
     ///
     /// # Examples
@@ -187,6 +483,45 @@ impl<T> Deque<T> {
         self.len_free
     }
 
+    /// The generation that will be given to the next item added to
+    /// the deque. Every item added to the deque consumes one
+    /// generation.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use token_deque::Deque;
+    ///
+    /// let mut d: Deque<u8> = Deque::new();
+    /// assert_eq!(0, d.next_generation());
+    ///
+    /// d.push_front(1);
+    /// d.pop_front();
+    /// d.push_front(2);
+    /// assert_eq!(2, d.next_generation());
+    /// ```
+    pub fn next_generation(&self) -> usize {
+        self.next_generation
+    }
+
+    /// The number of generations left before adding an item to the
+    /// deque will panic. This is `usize::MAX - next_generation()`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use token_deque::Deque;
+    ///
+    /// let mut d: Deque<u8> = Deque::new();
+    /// assert_eq!(usize::MAX, d.remaining_generations());
+    ///
+    /// d.push_front(1);
+    /// assert_eq!(usize::MAX - 1, d.remaining_generations());
+    /// ```
+    pub fn remaining_generations(&self) -> usize {
+        usize::MAX - self.next_generation
+    }
+
     /// Insert `data` into the front of the deque.
     ///
     /// # Examples
@@ -267,6 +602,72 @@ impl<T> Deque<T> {
         }
     }
 
+    /// Insert `data` into the front of the deque, evicting the back of
+    /// the deque if the deque already holds `max_len` items. Returns
+    /// the token for `data` along with the evicted value, if any.
+    /// Tokens for the evicted value are no longer valid.
+    ///
+    /// The eviction happens before the insert so that the evicted
+    /// slot can be reused. At most one item is evicted per call, and a
+    /// `max_len` of zero behaves the same as a `max_len` of one.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use token_deque::Deque;
+    ///
+    /// let mut l = Deque::new();
+    /// l.push_front_bounded(1, 2);
+    /// l.push_front_bounded(2, 2);
+    ///
+    /// let (tok, evicted) = l.push_front_bounded(3, 2);
+    /// assert_eq!(Some(1), evicted);
+    /// assert_eq!(Some(&3), l.get(&tok));
+    /// assert_eq!(vec![&3, &2], l.iter_front().collect::<Vec<_>>());
+    /// ```
+    pub fn push_front_bounded(&mut self, data: T, max_len: usize) -> (Token, Option<T>) {
+        let evicted = if self.len() >= max_len {
+            self.pop_back()
+        } else {
+            None
+        };
+
+        (self.push_front(data), evicted)
+    }
+
+    /// Insert `data` into the back of the deque, evicting the front of
+    /// the deque if the deque already holds `max_len` items. Returns
+    /// the token for `data` along with the evicted value, if any.
+    /// Tokens for the evicted value are no longer valid.
+    ///
+    /// The eviction happens before the insert so that the evicted
+    /// slot can be reused. At most one item is evicted per call, and a
+    /// `max_len` of zero behaves the same as a `max_len` of one.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use token_deque::Deque;
+    ///
+    /// let mut l = Deque::new();
+    /// l.push_back_bounded(1, 2);
+    /// l.push_back_bounded(2, 2);
+    ///
+    /// let (tok, evicted) = l.push_back_bounded(3, 2);
+    /// assert_eq!(Some(1), evicted);
+    /// assert_eq!(Some(&3), l.get(&tok));
+    /// assert_eq!(vec![&2, &3], l.iter_front().collect::<Vec<_>>());
+    /// ```
+    pub fn push_back_bounded(&mut self, data: T, max_len: usize) -> (Token, Option<T>) {
+        let evicted = if self.len() >= max_len {
+            self.pop_front()
+        } else {
+            None
+        };
+
+        (self.push_back(data), evicted)
+    }
+
     /// Remove the front of the deque and return it. If the deque is
     /// empty, `None` is returned.
     ///
@@ -315,6 +716,58 @@ impl<T> Deque<T> {
         }
     }
 
+    /// Remove the front of the deque and return it along with the
+    /// token that referred to it. The returned token is stale, since
+    /// its item is gone, but it can be used to find the item in a side
+    /// index. If the deque is empty, `None` is returned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use token_deque::Deque;
+    ///
+    /// let mut l = Deque::new();
+    /// let tok = l.push_back(10);
+    /// l.push_back(20);
+    ///
+    /// assert_eq!(Some((tok, 10)), l.pop_front_with_token());
+    /// assert_eq!(None, l.get(&tok));
+    /// ```
+    pub fn pop_front_with_token(&mut self) -> Option<(Token, T)> {
+        if usize::MAX != self.front {
+            let token = self.token_at(self.front);
+            Some((token, self.remove_unchecked(self.front)))
+        } else {
+            None
+        }
+    }
+
+    /// Remove the back of the deque and return it along with the token
+    /// that referred to it. The returned token is stale, since its item
+    /// is gone, but it can be used to find the item in a side index. If
+    /// the deque is empty, `None` is returned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use token_deque::Deque;
+    ///
+    /// let mut l = Deque::new();
+    /// l.push_back(10);
+    /// let tok = l.push_back(20);
+    ///
+    /// assert_eq!(Some((tok, 20)), l.pop_back_with_token());
+    /// assert_eq!(None, l.get(&tok));
+    /// ```
+    pub fn pop_back_with_token(&mut self) -> Option<(Token, T)> {
+        if usize::MAX != self.back {
+            let token = self.token_at(self.back);
+            Some((token, self.remove_unchecked(self.back)))
+        } else {
+            None
+        }
+    }
+
     /// Get the front value of the deque. If the deque is empty, `None`
     /// is returned.
     ///
@@ -423,48 +876,53 @@ impl<T> Deque<T> {
         }
     }
 
-    /// Get a reference to the item associated with `token`. If the
-    /// item has been removed, then `None` will be returned.
+    /// Get the front value of the deque. This is an alias of
+    /// [`get_front`] for those used to `Vec` and `VecDeque`.
     ///
-    /// # Examples
+    /// [`get_front`]: #method.get_front
     ///
-    /// A valid token results in `Some(&T)`.
+    /// # Examples
     ///
     /// ```
     /// use token_deque::Deque;
     ///
     /// let mut l = Deque::new();
-    /// let tok = l.push_front(10);
+    /// l.push_back(10);
+    /// l.push_back(20);
     ///
-    /// assert_eq!(Some(&10), l.get(&tok));
+    /// assert_eq!(Some(&10), l.first());
     /// ```
+    pub fn first(&self) -> Option<&T> {
+        self.get_front()
+    }
+
+    /// Get the front value of the deque as a mutable reference. This
+    /// is an alias of [`get_front_mut`].
     ///
-    /// An invalid token results in `None`.
+    /// [`get_front_mut`]: #method.get_front_mut
+    ///
+    /// # Examples
     ///
     /// ```
     /// use token_deque::Deque;
     ///
     /// let mut l = Deque::new();
-    /// let tok = l.push_front(10);
+    /// l.push_back(10);
     ///
-    /// // This removes the 10 we just pushed which invalidates the
-    /// // tok returned by push_front.
-    /// l.pop_front();
+    /// if let Some(i) = l.first_mut() {
+    ///     *i += 10;
+    /// }
     ///
-    /// assert_eq!(None, l.get(&tok));
+    /// assert_eq!(Some(&20), l.first());
     /// ```
-    pub fn get(&self, token: &Token) -> Option<&T> {
-        let Token { ix, generation } = token;
-
-        self.slots
-            .get(*ix)
-            .and_then(|s| s.get_used())
-            .and_then(|u| u.as_generation(*generation))
-            .map(|u| u.data())
+    pub fn first_mut(&mut self) -> Option<&mut T> {
+        self.get_front_mut()
     }
 
-    /// Get a mutable reference to the item associted with `token`. If
-    /// the item has been removed, then `None` will be returned.
+    /// Get the back value of the deque. This is an alias of
+    /// [`get_back`] for those used to `Vec` and `VecDeque`.
+    ///
+    /// [`get_back`]: #method.get_back
     ///
     /// # Examples
     ///
@@ -472,25 +930,85 @@ impl<T> Deque<T> {
     /// use token_deque::Deque;
     ///
     /// let mut l = Deque::new();
-    /// let tok = l.push_front(10);
+    /// l.push_back(10);
+    /// l.push_back(20);
     ///
-    /// l.get_mut(&tok).map(|i| *i += 10);
+    /// assert_eq!(Some(&20), l.last());
+    /// ```
+    pub fn last(&self) -> Option<&T> {
+        self.get_back()
+    }
+
+    /// Get the back value of the deque as a mutable reference. This is
+    /// an alias of [`get_back_mut`].
+    ///
+    /// [`get_back_mut`]: #method.get_back_mut
+    ///
+    /// # Examples
     ///
-    /// assert_eq!(Some(&20), l.get(&tok));
     /// ```
-    pub fn get_mut(&mut self, token: &Token) -> Option<&mut T> {
-        let Token { ix, generation } = token;
+    /// use token_deque::Deque;
+    ///
+    /// let mut l = Deque::new();
+    /// l.push_back(10);
+    ///
+    /// if let Some(i) = l.last_mut() {
+    ///     *i += 10;
+    /// }
+    ///
+    /// assert_eq!(Some(&20), l.last());
+    /// ```
+    pub fn last_mut(&mut self) -> Option<&mut T> {
+        self.get_back_mut()
+    }
 
-        self.slots
-            .get_mut(*ix)
-            .and_then(|s| s.get_used_mut())
-            .and_then(|u| u.as_generation_mut(*generation))
-            .map(|u| u.data_mut())
+    /// Get mutable references to both the front and the back of the
+    /// deque at the same time. Since the front and the back are the
+    /// same item when the deque holds only one item, `None` is
+    /// returned unless the deque holds at least two items.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use token_deque::Deque;
+    ///
+    /// let mut l: Deque<u8> = (1..=3).collect();
+    ///
+    /// if let Some((front, back)) = l.get_front_back_mut() {
+    ///     *front += 10;
+    ///     *back += 10;
+    /// }
+    ///
+    /// assert_eq!(vec![&11, &2, &13], l.iter_front().collect::<Vec<_>>());
+    /// ```
+    pub fn get_front_back_mut(&mut self) -> Option<(&mut T, &mut T)> {
+        if self.len() < 2 {
+            return None;
+        }
+
+        let (front, back) = (self.front, self.back);
+        let (front, back) = if front < back {
+            let (l, r) = self.slots.split_at_mut(back);
+            (&mut l[front], &mut r[0])
+        } else {
+            let (l, r) = self.slots.split_at_mut(front);
+            (&mut r[0], &mut l[back])
+        };
+
+        Some((
+            front
+                .get_used_mut()
+                .expect("self.slots[self.front] should always be a used slot")
+                .data_mut(),
+            back.get_used_mut()
+                .expect("self.slots[self.back] should always be a used slot")
+                .data_mut(),
+        ))
     }
 
-    /// Remove the item associated with the specified token from the
-    /// deque. If the item has already been removed, `None` is
-    /// returned. This consumes the token.
+    /// Get mutable references to the items associated with each of
+    /// `tokens`, in the same order as `tokens`. If any token is stale,
+    /// or if two tokens refer to the same item, `None` is returned.
     ///
     /// # Examples
     ///
@@ -498,461 +1016,5854 @@ impl<T> Deque<T> {
     /// use token_deque::Deque;
     ///
     /// let mut l = Deque::new();
-    /// let tok = l.push_front(10);
+    /// let a = l.push_back(1);
+    /// let b = l.push_back(2);
+    /// let c = l.push_back(3);
     ///
-    /// assert_eq!(Some(10), l.remove(&tok));
-    /// assert_eq!(None, l.remove(&tok));
+    /// if let Some(v) = l.get_disjoint_mut(&[&c, &a]) {
+    ///     for i in v {
+    ///         *i *= 10;
+    ///     }
+    /// }
+    /// assert_eq!(vec![&10, &2, &30], l.iter_front().collect::<Vec<_>>());
+    /// assert_eq!(None, l.get_disjoint_mut(&[&a, &b, &a]));
     /// ```
-    pub fn remove(&mut self, token: &Token) -> Option<T> {
-        let Token { ix, generation } = token;
+    pub fn get_disjoint_mut(&mut self, tokens: &[&Token]) -> Option<Vec<&mut T>> {
+        let mut ixs = Vec::with_capacity(tokens.len());
+        for token in tokens {
+            ixs.push(self.index_of(token)?);
+        }
 
-        self.slots
-            .get(*ix)
-            .and_then(|s| s.get_used())
-            .and_then(|v| v.as_generation(*generation).map(|_| ix))
-            .map(|ix| self.remove_unchecked(*ix))
+        let mut sorted = ixs.clone();
+        sorted.sort_unstable();
+        if sorted.windows(2).any(|w| w[0] == w[1]) {
+            return None;
+        }
+
+        let slots = self.slots.as_mut_ptr();
+        Some(
+            ixs.into_iter()
+                .map(|ix| {
+                    // SAFETY: every index was checked to be a used slot
+                    // within `self.slots`, and no index appears twice, so
+                    // none of the references alias. `self` is mutably
+                    // borrowed for as long as the references live.
+                    let slot = unsafe { &mut *slots.add(ix) };
+                    slot.get_used_mut()
+                        .expect("self.slots[ix] should always be a used slot")
+                        .data_mut()
+                })
+                .collect(),
+        )
     }
 
-    /// Create an iterator over the deque starting from the front.
+    /// Get the tokens for the front and back of the deque. If the deque
+    /// holds a single item, both tokens refer to it. If the deque is
+    /// empty, `None` is returned.
     ///
     /// # Examples
     ///
     /// ```
     /// use token_deque::Deque;
     ///
-    /// let mut d: Deque<u8> = Deque::new();
+    /// let mut l = Deque::new();
+    /// let a = l.push_back(10);
+    /// let b = l.push_back(20);
     ///
-    /// d.push_back(1);
-    /// d.push_back(2);
-    /// d.push_back(3);
+    /// assert_eq!(Some((a, b)), l.front_back_tokens());
+    /// ```
+    pub fn front_back_tokens(&self) -> Option<(Token, Token)> {
+        if self.is_empty() {
+            None
+        } else {
+            Some((self.token_at(self.front), self.token_at(self.back)))
+        }
+    }
+
+    /// Get a mutable reference to the front value of the deque. If the
+    /// deque is empty, the value returned by `make` is pushed onto the
+    /// front first.
+    ///
+    /// # Examples
     ///
-    /// let v: Vec<&u8> = d.iter_front().collect();
-    /// assert_eq!(vec![&1, &2, &3], v);
     /// ```
-    pub fn iter_front(&self) -> IterFront<T> {
-        IterFront::new(self, self.front)
+    /// use token_deque::Deque;
+    ///
+    /// let mut l = Deque::new();
+    ///
+    /// *l.front_mut_or_insert(|| 0) += 1;
+    /// *l.front_mut_or_insert(|| 0) += 1;
+    /// assert_eq!(vec![&2], l.iter_front().collect::<Vec<_>>());
+    /// ```
+    pub fn front_mut_or_insert<F: FnOnce() -> T>(&mut self, make: F) -> &mut T {
+        if self.is_empty() {
+            self.push_front(make());
+        }
+
+        self.get_front_mut()
+            .expect("the deque is known to be non-empty")
     }
 
-    /// A draining iterator starting from the front position. All
-    /// drained slots are moved onto the free list.
+    /// Get a mutable reference to the back value of the deque. If the
+    /// deque is empty, the value returned by `make` is pushed onto the
+    /// back first.
     ///
     /// # Examples
     ///
     /// ```
     /// use token_deque::Deque;
     ///
-    /// let mut d: Deque<u8> = Deque::new();
+    /// let mut l = Deque::new();
     ///
-    /// d.push_back(1);
-    /// d.push_back(2);
-    /// d.push_back(3);
+    /// l.back_mut_or_insert(Vec::new).push(1);
+    /// l.back_mut_or_insert(Vec::new).push(2);
+    /// assert_eq!(vec![&vec![1, 2]], l.iter_front().collect::<Vec<_>>());
+    /// ```
+    pub fn back_mut_or_insert<F: FnOnce() -> T>(&mut self, make: F) -> &mut T {
+        if self.is_empty() {
+            self.push_back(make());
+        }
+
+        self.get_back_mut()
+            .expect("the deque is known to be non-empty")
+    }
+
+    /// Swap the values at the front and the back of the deque. The
+    /// links are left alone, so each token keeps its position and now
+    /// refers to the value that was at the other end. Nothing happens
+    /// if the deque holds fewer than two items.
+    ///
+    /// # Examples
     ///
-    /// let v: Vec<u8> = d.drain_front().collect();
-    /// assert_eq!(vec![1, 2, 3], v);
-    /// assert_eq!(3, d.len_freelist());
     /// ```
-    pub fn drain_front(&mut self) -> DrainFront<T> {
-        DrainFront::new(self, self.front)
+    /// use token_deque::Deque;
+    ///
+    /// let mut l: Deque<u8> = (1..=3).collect();
+    ///
+    /// l.swap_front_back();
+    /// assert_eq!(vec![&3, &2, &1], l.iter_front().collect::<Vec<_>>());
+    /// ```
+    pub fn swap_front_back(&mut self) {
+        if let Some((front, back)) = self.get_front_back_mut() {
+            std::mem::swap(front, back);
+        }
     }
 
-    /// Create an iterator over the deque starting from the back.
+    /// Get the [`Entry`] for `token`, which is occupied if the token
+    /// still refers to an item in the deque, and vacant otherwise.
+    ///
+    /// [`Entry`]: enum.Entry.html
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use token_deque::{Deque, Entry};
+    ///
+    /// let mut l = Deque::new();
+    /// let tok = l.push_back(1);
+    ///
+    /// assert!(matches!(l.entry(&tok), Entry::Occupied(_)));
+    /// l.remove(&tok);
+    /// assert!(matches!(l.entry(&tok), Entry::Vacant(_)));
+    /// ```
+    pub fn entry(&mut self, token: &Token) -> Entry<'_, T> {
+        match self.index_of(token) {
+            Some(ix) => Entry::Occupied(
+                self.slots[ix]
+                    .get_used_mut()
+                    .expect("self.slots[ix] should always be a used slot")
+                    .data_mut(),
+            ),
+            None => Entry::Vacant(VacantEntry::new(self)),
+        }
+    }
+
+    /// Get a reference to the item associated with `token`. If the
+    /// item has been removed, then `None` will be returned.
     ///
     /// # Examples
     ///
+    /// A valid token results in `Some(&T)`.
+    ///
     /// ```
     /// use token_deque::Deque;
     ///
-    /// let mut d: Deque<u8> = Deque::new();
+    /// let mut l = Deque::new();
+    /// let tok = l.push_front(10);
     ///
-    /// d.push_back(1);
-    /// d.push_back(2);
-    /// d.push_back(3);
+    /// assert_eq!(Some(&10), l.get(&tok));
+    /// ```
+    ///
+    /// An invalid token results in `None`.
     ///
-    /// let v: Vec<&u8> = d.iter_back().collect();
-    /// assert_eq!(vec![&3, &2, &1], v);
     /// ```
-    pub fn iter_back(&self) -> IterBack<T> {
-        IterBack::new(self, self.back)
+    /// use token_deque::Deque;
+    ///
+    /// let mut l = Deque::new();
+    /// let tok = l.push_front(10);
+    ///
+    /// // This removes the 10 we just pushed which invalidates the
+    /// // tok returned by push_front.
+    /// l.pop_front();
+    ///
+    /// assert_eq!(None, l.get(&tok));
+    /// ```
+    pub fn get(&self, token: &Token) -> Option<&T> {
+        let Token { ix, generation } = token;
+
+        self.slots
+            .get(*ix)
+            .and_then(|s| s.get_used())
+            .and_then(|u| u.as_generation(*generation))
+            .map(|u| u.data())
     }
 
-    /// A draining iterator starting from the back position. All
-    /// drained slots are moved onto the free list.
+    /// Get a mutable reference to the item associted with `token`. If
+    /// the item has been removed, then `None` will be returned.
     ///
     /// # Examples
     ///
     /// ```
     /// use token_deque::Deque;
     ///
-    /// let mut d: Deque<u8> = Deque::new();
+    /// let mut l = Deque::new();
+    /// let tok = l.push_front(10);
     ///
-    /// d.push_back(1);
-    /// d.push_back(2);
-    /// d.push_back(3);
+    /// l.get_mut(&tok).map(|i| *i += 10);
     ///
-    /// let v: Vec<u8> = d.drain_back().collect();
-    /// assert_eq!(vec![3, 2, 1], v);
-    /// assert_eq!(3, d.len_freelist());
+    /// assert_eq!(Some(&20), l.get(&tok));
     /// ```
-    pub fn drain_back(&mut self) -> DrainBack<T> {
-        DrainBack::new(self, self.back)
+    pub fn get_mut(&mut self, token: &Token) -> Option<&mut T> {
+        let Token { ix, generation } = token;
+
+        self.slots
+            .get_mut(*ix)
+            .and_then(|s| s.get_used_mut())
+            .and_then(|u| u.as_generation_mut(*generation))
+            .map(|u| u.data_mut())
     }
 
-    fn remove_unchecked(&mut self, ix: usize) -> T {
-        let (front, data, back) = self
-            .free(ix)
-            .into_used()
-            .expect("self.slots[ix] must be used in order to remove it")
-            .take();
+    /// Replace the item associated with `token` with `data`, returning
+    /// the old item. If the item has been removed, `None` is returned
+    /// and the deque is left unchanged. The token remains valid and
+    /// refers to `data` afterward.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use token_deque::Deque;
+    ///
+    /// let mut l = Deque::new();
+    /// let tok = l.push_front(10);
+    ///
+    /// assert_eq!(Some(10), l.replace(&tok, 20));
+    /// assert_eq!(Some(&20), l.get(&tok));
+    /// ```
+    pub fn replace(&mut self, token: &Token, data: T) -> Option<T> {
+        self.get_mut(token).map(|v| std::mem::replace(v, data))
+    }
 
-        if self.front == ix {
-            debug_assert_eq!(usize::MAX, front);
-            self.front = back;
-        } else {
-            debug_assert_ne!(usize::MAX, front);
-            self.slots[front]
-                .get_used_mut()
-                .expect("self.slots[front] should always be a used slot")
-                .set_back(back);
+    /// Run `f` on a mutable reference to the item associated with
+    /// `token`. Returns `true` if the token was valid and `f` was run,
+    /// or `false` if the item has been removed, in which case `f` is
+    /// not run.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use token_deque::Deque;
+    ///
+    /// let mut l = Deque::new();
+    /// let tok = l.push_front(10);
+    ///
+    /// assert!(l.update(&tok, |i| *i += 10));
+    /// assert_eq!(Some(&20), l.get(&tok));
+    /// ```
+    pub fn update<F: FnOnce(&mut T)>(&mut self, token: &Token, f: F) -> bool {
+        match self.get_mut(token) {
+            Some(v) => {
+                f(v);
+                true
+            }
+            None => false,
         }
+    }
 
-        if self.back == ix {
-            debug_assert_eq!(usize::MAX, back);
-            self.back = front;
-        } else {
-            debug_assert_ne!(usize::MAX, back);
-            self.slots[back]
-                .get_used_mut()
-                .expect("self.slots[back] should always be a used slot")
-                .set_front(front);
-        }
+    /// Get a mutable reference to the item associated with `token`.
+    /// If the item has been removed, the result of `make` is pushed
+    /// onto the front of the deque instead. Returns the token for the
+    /// item along with the reference, which is `token` itself unless a
+    /// new item was pushed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use token_deque::Deque;
+    ///
+    /// let mut l = Deque::new();
+    /// let tok = l.push_back(10);
+    /// l.remove(&tok);
+    ///
+    /// let (new_tok, v) = l.get_or_insert_front(&tok, || 20);
+    /// assert_eq!(20, *v);
+    /// assert_ne!(tok, new_tok);
+    /// assert_eq!(Some(&20), l.get(&new_tok));
+    /// ```
+    pub fn get_or_insert_front<F: FnOnce() -> T>(
+        &mut self,
+        token: &Token,
+        make: F,
+    ) -> (Token, &mut T) {
+        let token = match self.index_of(token) {
+            Some(_) => *token,
+            None => self.push_front(make()),
+        };
 
-        data
+        let data = self.get_mut(&token).expect("token is known to be valid");
+        (token, data)
     }
 
-    pub(crate) fn allocate(&mut self, front: usize, back: usize, data: T) -> (usize, usize) {
-        // Assuming a 64 bit usize and that we could add a new item to
-        // the deque 10 billion times per second, it would take ~58
-        // years for the generation to overflow. After that point, the
-        // token that is constructed from the generation could be used
-        // to remove or get an incorrect object from the deque if the
-        // object at that index had the same generation 58 years
-        // prior.
-        //
-        // We do a checked-add in order to save future developers from
-        // having to hunt down this rare problem in ancient code
-        // bases. Instead, we give them a once-in-a-lifetime panic.
-        let generation = self.next_generation;
-        self.next_generation = self
-            .next_generation
-            .checked_add(1)
-            .expect("58 years have passed, or generations have become corrupted");
+    /// Get a mutable reference to the item associated with `token`.
+    /// If the item has been removed, the result of `make` is pushed
+    /// onto the back of the deque instead. Returns the token for the
+    /// item along with the reference, which is `token` itself unless a
+    /// new item was pushed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use token_deque::Deque;
+    ///
+    /// let mut l = Deque::new();
+    /// let tok = l.push_back(10);
+    ///
+    /// let (same_tok, v) = l.get_or_insert_back(&tok, || 20);
+    /// assert_eq!(10, *v);
+    /// assert_eq!(tok, same_tok);
+    /// ```
+    pub fn get_or_insert_back<F: FnOnce() -> T>(
+        &mut self,
+        token: &Token,
+        make: F,
+    ) -> (Token, &mut T) {
+        let token = match self.index_of(token) {
+            Some(_) => *token,
+            None => self.push_back(make()),
+        };
+
+        let data = self.get_mut(&token).expect("token is known to be valid");
+        (token, data)
+    }
+
+    /// Remove the item associated with the specified token from the
+    /// deque. If the item has already been removed, `None` is
+    /// returned. This consumes the token.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use token_deque::Deque;
+    ///
+    /// let mut l = Deque::new();
+    /// let tok = l.push_front(10);
+    ///
+    /// assert_eq!(Some(10), l.remove(&tok));
+    /// assert_eq!(None, l.remove(&tok));
+    /// ```
+    pub fn remove(&mut self, token: &Token) -> Option<T> {
+        let Token { ix, generation } = token;
+
+        self.slots
+            .get(*ix)
+            .and_then(|s| s.get_used())
+            .and_then(|v| v.as_generation(*generation).map(|_| ix))
+            .map(|ix| self.remove_unchecked(*ix))
+    }
+
+    /// Remove the items associated with each of `tokens`, in order,
+    /// returning the removed items in a `Vec` that lines up with
+    /// `tokens`. A token whose item has already been removed, including
+    /// a token that appears earlier in the same slice, yields `None`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use token_deque::Deque;
+    ///
+    /// let mut l = Deque::new();
+    /// let a = l.push_back(1);
+    /// l.push_back(2);
+    /// let c = l.push_back(3);
+    ///
+    /// assert_eq!(vec![Some(3), Some(1), None], l.remove_many(&[c, a, c]));
+    /// assert_eq!(vec![&2], l.iter_front().collect::<Vec<_>>());
+    /// ```
+    pub fn remove_many(&mut self, tokens: &[Token]) -> Vec<Option<T>> {
+        tokens.iter().map(|t| self.remove(t)).collect()
+    }
+
+    /// Replace the items associated with each of `tokens` with the
+    /// matching item of `values`, returning the old items in a `Vec`
+    /// that lines up with `tokens`. A stale token yields `None`, and
+    /// its new value is dropped. All tokens remain valid.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `tokens` and `values` have different lengths.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use token_deque::Deque;
+    ///
+    /// let mut l = Deque::new();
+    /// let a = l.push_back(1);
+    /// let b = l.push_back(2);
+    ///
+    /// assert_eq!(vec![Some(2), Some(1)], l.replace_many(&[b, a], vec![20, 10]));
+    /// assert_eq!(vec![&10, &20], l.iter_front().collect::<Vec<_>>());
+    /// ```
+    pub fn replace_many(&mut self, tokens: &[Token], values: Vec<T>) -> Vec<Option<T>> {
+        assert_eq!(
+            tokens.len(),
+            values.len(),
+            "tokens and values must have the same length"
+        );
+
+        tokens
+            .iter()
+            .zip(values)
+            .map(|(t, v)| self.replace(t, v))
+            .collect()
+    }
+
+    /// Remove the item associated with `token` only if `pred` returns
+    /// `true` for it. If the item has already been removed, or `pred`
+    /// returns `false`, `None` is returned and the deque is left
+    /// unchanged.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use token_deque::Deque;
+    ///
+    /// let mut l = Deque::new();
+    /// let tok = l.push_front(10);
+    ///
+    /// assert_eq!(None, l.remove_if(&tok, |i| *i > 10));
+    /// assert_eq!(Some(10), l.remove_if(&tok, |i| *i == 10));
+    /// ```
+    pub fn remove_if<F: FnOnce(&T) -> bool>(&mut self, token: &Token, pred: F) -> Option<T> {
+        let ix = self.index_of(token)?;
+        let remove = pred(
+            self.slots[ix]
+                .get_used()
+                .expect("self.slots[ix] should always be a used slot")
+                .data(),
+        );
+
+        if remove {
+            Some(self.remove_unchecked(ix))
+        } else {
+            None
+        }
+    }
+
+    /// Remove the item associated with `token` from the deque and move
+    /// the front of the deque into the position it occupied. This is
+    /// similar to `Vec::swap_remove`: the removed position is filled,
+    /// and the front of the deque shifts inward by one. If the item
+    /// has already been removed, `None` is returned and the deque is
+    /// left unchanged.
+    ///
+    /// Tokens for all other items remain valid.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use token_deque::Deque;
+    ///
+    /// let mut l = Deque::new();
+    /// l.push_back(1);
+    /// l.push_back(2);
+    /// let tok = l.push_back(3);
+    /// l.push_back(4);
+    ///
+    /// assert_eq!(Some(3), l.swap_remove_front(&tok));
+    /// assert_eq!(vec![&2, &1, &4], l.iter_front().collect::<Vec<_>>());
+    /// ```
+    pub fn swap_remove_front(&mut self, token: &Token) -> Option<T> {
+        let ix = self.index_of(token)?;
+        let filler = self.front;
+
+        if filler != ix {
+            self.unlink(filler);
+        }
+
+        let (front, back) = {
+            let u = self.slots[ix]
+                .get_used()
+                .expect("self.slots[ix] should always be a used slot");
+            (u.front(), u.back())
+        };
+        let data = self.remove_unchecked(ix);
+
+        if filler != ix {
+            self.link_between(filler, front, back);
+        }
+
+        Some(data)
+    }
+
+    /// Remove the item associated with `token` from the deque and move
+    /// the back of the deque into the position it occupied. This is
+    /// the mirror image of [`swap_remove_front`]. If the item has
+    /// already been removed, `None` is returned and the deque is left
+    /// unchanged.
+    ///
+    /// [`swap_remove_front`]: #method.swap_remove_front
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use token_deque::Deque;
+    ///
+    /// let mut l = Deque::new();
+    /// l.push_back(1);
+    /// let tok = l.push_back(2);
+    /// l.push_back(3);
+    /// l.push_back(4);
+    ///
+    /// assert_eq!(Some(2), l.swap_remove_back(&tok));
+    /// assert_eq!(vec![&1, &4, &3], l.iter_front().collect::<Vec<_>>());
+    /// ```
+    pub fn swap_remove_back(&mut self, token: &Token) -> Option<T> {
+        let ix = self.index_of(token)?;
+        let filler = self.back;
+
+        if filler != ix {
+            self.unlink(filler);
+        }
+
+        let (front, back) = {
+            let u = self.slots[ix]
+                .get_used()
+                .expect("self.slots[ix] should always be a used slot");
+            (u.front(), u.back())
+        };
+        let data = self.remove_unchecked(ix);
+
+        if filler != ix {
+            self.link_between(filler, front, back);
+        }
+
+        Some(data)
+    }
+
+    /// Move the item associated with `token` to the front of the
+    /// deque. Returns `true` if the token was valid and the item was
+    /// moved, or `false` if the item has already been removed. The
+    /// token remains valid after the move.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use token_deque::Deque;
+    ///
+    /// let mut l = Deque::new();
+    /// l.push_back(1);
+    /// let tok = l.push_back(2);
+    /// l.push_back(3);
+    ///
+    /// assert!(l.touch(&tok));
+    /// assert_eq!(vec![&2, &1, &3], l.iter_front().collect::<Vec<_>>());
+    /// assert_eq!(Some(&2), l.get(&tok));
+    /// ```
+    pub fn touch(&mut self, token: &Token) -> bool {
+        match self.index_of(token) {
+            Some(ix) => {
+                if self.front != ix {
+                    self.unlink(ix);
+                    self.link_between(ix, usize::MAX, self.front);
+                }
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Returns `true` if `token` still refers to an item in the deque.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use token_deque::Deque;
+    ///
+    /// let mut l = Deque::new();
+    /// let tok = l.push_back(1);
+    ///
+    /// assert!(l.contains_token(&tok));
+    /// l.remove(&tok);
+    /// assert!(!l.contains_token(&tok));
+    /// ```
+    pub fn contains_token(&self, token: &Token) -> bool {
+        self.index_of(token).is_some()
+    }
+
+    /// Check each of `tokens`, returning a `Vec` that lines up with
+    /// `tokens` and holds `true` for each token that no longer refers
+    /// to an item in the deque.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use token_deque::Deque;
+    ///
+    /// let mut l = Deque::new();
+    /// let a = l.push_back(1);
+    /// let b = l.push_back(2);
+    /// l.remove(&a);
+    ///
+    /// assert_eq!(vec![true, false], l.stale_tokens(&[a, b]));
+    /// ```
+    pub fn stale_tokens(&self, tokens: &[Token]) -> Vec<bool> {
+        tokens.iter().map(|t| self.index_of(t).is_none()).collect()
+    }
+
+    /// Get the generation of the item in the slot at index `ix` of the
+    /// underlying `Vec`. If the slot is free, or out of bounds, `None`
+    /// is returned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use token_deque::Deque;
+    ///
+    /// let mut l = Deque::new();
+    /// l.push_back(1);
+    /// l.push_back(2);
+    ///
+    /// assert_eq!(Some(1), l.slot_generation(1));
+    /// assert_eq!(None, l.slot_generation(2));
+    /// ```
+    pub fn slot_generation(&self, ix: usize) -> Option<usize> {
+        self.slots
+            .get(ix)
+            .and_then(|s| s.get_used())
+            .map(|u| u.generation())
+    }
+
+    /// Returns `true` if the slot that `token` points at is used, and
+    /// its generation matches the token. This is `false` once the item
+    /// is removed, even if the slot has since been reused by another
+    /// item. It agrees with [`contains_token`].
+    ///
+    /// [`contains_token`]: #method.contains_token
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use token_deque::Deque;
+    ///
+    /// let mut l = Deque::new();
+    /// let old = l.push_back(1);
+    /// l.remove(&old);
+    /// let new = l.push_back(2);
+    ///
+    /// assert!(!l.is_current(&old));
+    /// assert!(l.is_current(&new));
+    /// ```
+    pub fn is_current(&self, token: &Token) -> bool {
+        self.slot_generation(token.ix) == Some(token.generation)
+    }
+
+    /// Returns `true` if `token` refers to the item at the front of the
+    /// deque. Stale tokens are never at the front.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use token_deque::Deque;
+    ///
+    /// let mut l = Deque::new();
+    /// let a = l.push_back(1);
+    /// let b = l.push_back(2);
+    ///
+    /// assert!(l.is_front(&a));
+    /// assert!(!l.is_front(&b));
+    /// ```
+    pub fn is_front(&self, token: &Token) -> bool {
+        self.index_of(token) == Some(self.front)
+    }
+
+    /// Returns `true` if `token` refers to the item at the back of the
+    /// deque. Stale tokens are never at the back.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use token_deque::Deque;
+    ///
+    /// let mut l = Deque::new();
+    /// let a = l.push_back(1);
+    /// let b = l.push_back(2);
+    ///
+    /// assert!(l.is_back(&b));
+    /// assert!(!l.is_back(&a));
+    /// ```
+    pub fn is_back(&self, token: &Token) -> bool {
+        self.index_of(token) == Some(self.back)
+    }
+
+    /// Create an iterator over the deque starting from the front.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use token_deque::Deque;
+    ///
+    /// let mut d: Deque<u8> = Deque::new();
+    ///
+    /// d.push_back(1);
+    /// d.push_back(2);
+    /// d.push_back(3);
+    ///
+    /// let v: Vec<&u8> = d.iter_front().collect();
+    /// assert_eq!(vec![&1, &2, &3], v);
+    /// ```
+    pub fn iter_front(&self) -> IterFront<'_, T> {
+        IterFront::new(self, self.front)
+    }
+
+    /// A draining iterator starting from the front position. All
+    /// drained slots are moved onto the free list.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use token_deque::Deque;
+    ///
+    /// let mut d: Deque<u8> = Deque::new();
+    ///
+    /// d.push_back(1);
+    /// d.push_back(2);
+    /// d.push_back(3);
+    ///
+    /// let v: Vec<u8> = d.drain_front().collect();
+    /// assert_eq!(vec![1, 2, 3], v);
+    /// assert_eq!(3, d.len_freelist());
+    /// ```
+    pub fn drain_front(&mut self) -> DrainFront<'_, T> {
+        DrainFront::new(self, self.front)
+    }
+
+    /// Create an iterator over the deque starting from the back.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use token_deque::Deque;
+    ///
+    /// let mut d: Deque<u8> = Deque::new();
+    ///
+    /// d.push_back(1);
+    /// d.push_back(2);
+    /// d.push_back(3);
+    ///
+    /// let v: Vec<&u8> = d.iter_back().collect();
+    /// assert_eq!(vec![&3, &2, &1], v);
+    /// ```
+    pub fn iter_back(&self) -> IterBack<'_, T> {
+        IterBack::new(self, self.back)
+    }
+
+    /// A draining iterator starting from the back position. All
+    /// drained slots are moved onto the free list.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use token_deque::Deque;
+    ///
+    /// let mut d: Deque<u8> = Deque::new();
+    ///
+    /// d.push_back(1);
+    /// d.push_back(2);
+    /// d.push_back(3);
+    ///
+    /// let v: Vec<u8> = d.drain_back().collect();
+    /// assert_eq!(vec![3, 2, 1], v);
+    /// assert_eq!(3, d.len_freelist());
+    /// ```
+    pub fn drain_back(&mut self) -> DrainBack<'_, T> {
+        DrainBack::new(self, self.back)
+    }
+
+    /// Retain only the items for which `f` returns `true`. Items are
+    /// visited from front to back, and removed items have their slots
+    /// moved onto the free list.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use token_deque::Deque;
+    ///
+    /// let mut d: Deque<u8> = (1..=4).collect();
+    ///
+    /// d.retain(|i| 0 == *i % 2);
+    /// assert_eq!(vec![&2, &4], d.iter_front().collect::<Vec<_>>());
+    /// ```
+    pub fn retain<F: FnMut(&T) -> bool>(&mut self, f: F) {
+        self.retain_count(f);
+    }
+
+    /// Retain only the items for which `f` returns `true`, like
+    /// [`retain`], and return how many items were removed.
+    ///
+    /// [`retain`]: #method.retain
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use token_deque::Deque;
+    ///
+    /// let mut d: Deque<u8> = (1..=4).collect();
+    ///
+    /// assert_eq!(2, d.retain_count(|i| 0 == *i % 2));
+    /// assert_eq!(vec![&2, &4], d.iter_front().collect::<Vec<_>>());
+    /// ```
+    pub fn retain_count<F: FnMut(&T) -> bool>(&mut self, mut f: F) -> usize {
+        let len = self.len();
+        self.retain_mut(|i| f(i));
+        len - self.len()
+    }
+
+    /// Retain only the items for which `f` returns `true`, passing
+    /// each item to `f` as a mutable reference. Items are visited from
+    /// front to back. Any changes `f` makes to retained items are
+    /// kept, and removed items have their slots moved onto the free
+    /// list.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use token_deque::Deque;
+    ///
+    /// let mut d: Deque<u8> = (1..=4).collect();
+    ///
+    /// d.retain_mut(|i| {
+    ///     *i += 1;
+    ///     0 == *i % 2
+    /// });
+    /// assert_eq!(vec![&2, &4], d.iter_front().collect::<Vec<_>>());
+    /// ```
+    pub fn retain_mut<F: FnMut(&mut T) -> bool>(&mut self, mut f: F) {
+        let mut ix = self.front;
+
+        while usize::MAX != ix {
+            let u = self.slots[ix]
+                .get_used_mut()
+                .expect("self.slots[ix] should always be a used slot");
+            let next = u.back();
+
+            if !f(u.data_mut()) {
+                self.remove_unchecked(ix);
+            }
+
+            ix = next;
+        }
+    }
+
+    /// Retain only the items for which `f` returns `true`, like
+    /// [`retain`], but visit the items from back to front. This only
+    /// makes a difference when `f` keeps state between calls.
+    ///
+    /// [`retain`]: #method.retain
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use token_deque::Deque;
+    ///
+    /// let mut d: Deque<u8> = (1..=4).collect();
+    ///
+    /// let mut sum = 0;
+    /// d.retain_from_back(|i| {
+    ///     sum += *i;
+    ///     sum <= 5
+    /// });
+    /// assert_eq!(vec![&4], d.iter_front().collect::<Vec<_>>());
+    /// ```
+    pub fn retain_from_back<F: FnMut(&T) -> bool>(&mut self, mut f: F) {
+        let mut ix = self.back;
+
+        while usize::MAX != ix {
+            let u = self.slots[ix]
+                .get_used()
+                .expect("self.slots[ix] should always be a used slot");
+            let next = u.front();
+
+            if !f(u.data()) {
+                self.remove_unchecked(ix);
+            }
+
+            ix = next;
+        }
+    }
+
+    /// Call `f` on a mutable reference to every item of the deque,
+    /// from front to back.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use token_deque::Deque;
+    ///
+    /// let mut d: Deque<u8> = (1..=3).collect();
+    ///
+    /// d.apply_all(|i| *i *= *i);
+    /// assert_eq!(vec![&1, &4, &9], d.iter_front().collect::<Vec<_>>());
+    /// ```
+    pub fn apply_all<F: FnMut(&mut T)>(&mut self, mut f: F) {
+        let mut ix = self.front;
+
+        while usize::MAX != ix {
+            let u = self.slots[ix]
+                .get_used_mut()
+                .expect("self.slots[ix] should always be a used slot");
+            f(u.data_mut());
+            ix = u.back();
+        }
+    }
+
+    /// Walk the deque from front to back, calling `f` on each item and
+    /// acting on the [`PruneAction`] it returns. Items are kept or
+    /// removed one at a time, and the walk ends early on
+    /// `PruneAction::Stop`. Since `f` can keep state between calls, it
+    /// can decide based on the items that came before.
+    ///
+    /// [`PruneAction`]: enum.PruneAction.html
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use token_deque::{Deque, PruneAction};
+    ///
+    /// let mut d: Deque<u8> = vec![1, 2, 0, 3, 4].into_iter().collect();
+    ///
+    /// d.prune(|i| match *i {
+    ///     0 => PruneAction::Stop,
+    ///     i if i % 2 == 0 => PruneAction::Remove,
+    ///     _ => PruneAction::Keep,
+    /// });
+    /// assert_eq!(vec![&1, &0, &3, &4], d.iter_front().collect::<Vec<_>>());
+    /// ```
+    pub fn prune<F: FnMut(&mut T) -> PruneAction>(&mut self, mut f: F) {
+        let mut ix = self.front;
+
+        while usize::MAX != ix {
+            let u = self.slots[ix]
+                .get_used_mut()
+                .expect("self.slots[ix] should always be a used slot");
+            let next = u.back();
+
+            match f(u.data_mut()) {
+                PruneAction::Keep => {}
+                PruneAction::Remove => {
+                    self.remove_unchecked(ix);
+                }
+                PruneAction::Stop => return,
+            }
+
+            ix = next;
+        }
+    }
+
+    /// Fold the items of the deque, from front to back, into an
+    /// accumulator starting at `init`, giving `f` a mutable reference
+    /// to each item so that it can be changed along the way.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use token_deque::Deque;
+    ///
+    /// let mut d: Deque<u8> = (1..=4).collect();
+    ///
+    /// let total = d.fold_mut(0, |acc, i| {
+    ///     *i += acc;
+    ///     *i
+    /// });
+    /// assert_eq!(10, total);
+    /// assert_eq!(vec![&1, &3, &6, &10], d.iter_front().collect::<Vec<_>>());
+    /// ```
+    pub fn fold_mut<B, F: FnMut(B, &mut T) -> B>(&mut self, init: B, mut f: F) -> B {
+        let mut acc = init;
+        let mut ix = self.front;
+
+        while usize::MAX != ix {
+            let u = self.slots[ix]
+                .get_used_mut()
+                .expect("self.slots[ix] should always be a used slot");
+            acc = f(acc, u.data_mut());
+            ix = u.back();
+        }
+
+        acc
+    }
+
+    /// A draining iterator over the items from `start` through `end`,
+    /// inclusive, moving from front to back. Returns `None` if either
+    /// token is no longer valid, or if `start` comes after `end` in
+    /// the deque. Drained slots are moved onto the free list, and the
+    /// whole range is removed even if the iterator is dropped early.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use token_deque::Deque;
+    ///
+    /// let mut d: Deque<u8> = Deque::new();
+    ///
+    /// d.push_back(1);
+    /// let start = d.push_back(2);
+    /// d.push_back(3);
+    /// let end = d.push_back(4);
+    /// d.push_back(5);
+    ///
+    /// let v: Vec<u8> = d.drain_range(&start, &end).unwrap().collect();
+    /// assert_eq!(vec![2, 3, 4], v);
+    /// assert_eq!(vec![&1, &5], d.iter_front().collect::<Vec<_>>());
+    /// ```
+    pub fn drain_range(&mut self, start: &Token, end: &Token) -> Option<DrainRange<'_, T>> {
+        let start = self.index_of(start)?;
+        let end = self.index_of(end)?;
+
+        if self.is_ordered(start, end) {
+            Some(DrainRange::new(self, start, end))
+        } else {
+            None
+        }
+    }
+
+    /// Like [`retain`], but only the items from `start` through `end`,
+    /// inclusive, are passed to `f` and may be removed. Items outside
+    /// of the range, and their tokens, are left alone. Returns `false`
+    /// and leaves the deque unchanged if either token is no longer
+    /// valid, or if `start` comes after `end` in the deque.
+    ///
+    /// [`retain`]: #method.retain
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use token_deque::Deque;
+    ///
+    /// let mut d = Deque::new();
+    /// d.push_back(1);
+    /// let start = d.push_back(2);
+    /// d.push_back(3);
+    /// let end = d.push_back(4);
+    /// d.push_back(6);
+    ///
+    /// assert!(d.retain_range(&start, &end, |i| i % 2 == 1));
+    /// assert_eq!(vec![&1, &3, &6], d.iter_front().collect::<Vec<_>>());
+    /// ```
+    pub fn retain_range<F: FnMut(&T) -> bool>(
+        &mut self,
+        start: &Token,
+        end: &Token,
+        mut f: F,
+    ) -> bool {
+        let (start, end) = match (self.index_of(start), self.index_of(end)) {
+            (Some(start), Some(end)) if self.is_ordered(start, end) => (start, end),
+            _ => return false,
+        };
+
+        let mut ix = start;
+        loop {
+            let u = self.slots[ix]
+                .get_used()
+                .expect("self.slots[ix] should always be a used slot");
+            let next = u.back();
+
+            if !f(u.data()) {
+                self.remove_unchecked(ix);
+            }
+
+            if ix == end {
+                return true;
+            }
+            ix = next;
+        }
+    }
+
+    /// Consume the deque, splitting it into two deques. The first
+    /// holds the items for which `f` returns `true`, and the second
+    /// holds the rest. Items keep their relative order in each deque.
+    /// Tokens from the original deque are not valid for either of the
+    /// new deques.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use token_deque::Deque;
+    ///
+    /// let d: Deque<u8> = (1..=4).collect();
+    ///
+    /// let (even, odd) = d.partition(|i| 0 == *i % 2);
+    /// assert_eq!(vec![&2, &4], even.iter_front().collect::<Vec<_>>());
+    /// assert_eq!(vec![&1, &3], odd.iter_front().collect::<Vec<_>>());
+    /// ```
+    pub fn partition<F: FnMut(&T) -> bool>(mut self, mut f: F) -> (Deque<T>, Deque<T>) {
+        let mut matches = Deque::new();
+        let mut rest = Deque::new();
+
+        for i in self.drain_front() {
+            if f(&i) {
+                matches.push_back(i);
+            } else {
+                rest.push_back(i);
+            }
+        }
+
+        (matches, rest)
+    }
+
+    /// Rotate the deque `n` places to the left, so that the first `n`
+    /// items move to the back of the deque. Only links are rewritten,
+    /// so all tokens remain valid. `n` is taken modulo `len()`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use token_deque::Deque;
+    ///
+    /// let mut d: Deque<u8> = (1..=5).collect();
+    ///
+    /// d.rotate_left(2);
+    /// assert_eq!(vec![&3, &4, &5, &1, &2], d.iter_front().collect::<Vec<_>>());
+    /// ```
+    pub fn rotate_left(&mut self, n: usize) {
+        if self.is_empty() {
+            return;
+        }
+
+        let n = n % self.len();
+        if 0 != n {
+            let ix = self.nth_index(n);
+            self.rotate_to(ix);
+        }
+    }
+
+    /// Rotate the deque `n` places to the right, so that the last `n`
+    /// items move to the front of the deque. Only links are rewritten,
+    /// so all tokens remain valid. `n` is taken modulo `len()`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use token_deque::Deque;
+    ///
+    /// let mut d: Deque<u8> = (1..=5).collect();
+    ///
+    /// d.rotate_right(1);
+    /// assert_eq!(vec![&5, &1, &2, &3, &4], d.iter_front().collect::<Vec<_>>());
+    /// ```
+    pub fn rotate_right(&mut self, n: usize) {
+        if self.is_empty() {
+            return;
+        }
+
+        let n = n % self.len();
+        if 0 != n {
+            let ix = self.nth_index_back(n - 1);
+            self.rotate_to(ix);
+        }
+    }
+
+    /// Rotate the deque by `by` places. A positive `by` rotates to the
+    /// left, like [`rotate_left`], and a negative `by` rotates to the
+    /// right, like [`rotate_right`]. Only links are rewritten, so all
+    /// tokens remain valid. `by` is taken modulo `len()`.
+    ///
+    /// [`rotate_left`]: #method.rotate_left
+    /// [`rotate_right`]: #method.rotate_right
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use token_deque::Deque;
+    ///
+    /// let mut d: Deque<u8> = (1..=4).collect();
+    ///
+    /// d.rotate(1);
+    /// assert_eq!(vec![&2, &3, &4, &1], d.iter_front().collect::<Vec<_>>());
+    /// d.rotate(-2);
+    /// assert_eq!(vec![&4, &1, &2, &3], d.iter_front().collect::<Vec<_>>());
+    /// ```
+    pub fn rotate(&mut self, by: isize) {
+        if by < 0 {
+            self.rotate_right(by.unsigned_abs());
+        } else {
+            self.rotate_left(by.unsigned_abs());
+        }
+    }
+
+    /// Rotate the deque so that the item associated with `token` is at
+    /// the front. The items that were in front of it wrap around to
+    /// the back. Only links are rewritten, so all tokens remain valid.
+    /// Returns `false` if the item has already been removed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use token_deque::Deque;
+    ///
+    /// let mut d = Deque::new();
+    /// d.push_back(1);
+    /// let tok = d.push_back(2);
+    /// d.push_back(3);
+    ///
+    /// assert!(d.rotate_to_front(&tok));
+    /// assert_eq!(vec![&2, &3, &1], d.iter_front().collect::<Vec<_>>());
+    /// ```
+    pub fn rotate_to_front(&mut self, token: &Token) -> bool {
+        match self.index_of(token) {
+            Some(ix) => {
+                self.rotate_to(ix);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Rotate the deque so that the item associated with `token` is at
+    /// the back. The items that were behind it wrap around to the
+    /// front. Only links are rewritten, so all tokens remain valid.
+    /// Returns `false` if the item has already been removed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use token_deque::Deque;
+    ///
+    /// let mut d = Deque::new();
+    /// d.push_back(1);
+    /// let tok = d.push_back(2);
+    /// d.push_back(3);
+    ///
+    /// assert!(d.rotate_to_back(&tok));
+    /// assert_eq!(vec![&3, &1, &2], d.iter_front().collect::<Vec<_>>());
+    /// ```
+    pub fn rotate_to_back(&mut self, token: &Token) -> bool {
+        match self.index_of(token) {
+            Some(ix) => {
+                if self.back != ix {
+                    let (_, back) = self.links(ix);
+                    self.rotate_to(back);
+                }
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Split the deque in two at position `n`. The first `n` items
+    /// stay in `self`, and the remaining items are moved, in order,
+    /// into the returned deque. If `n` is zero, `self` is left empty,
+    /// and if `n` is at least `len()`, the returned deque is empty.
+    ///
+    /// Tokens for the items that are moved into the returned deque are
+    /// no longer valid, and the slots they used are moved onto the
+    /// free list of `self`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use token_deque::Deque;
+    ///
+    /// let mut d: Deque<u8> = (1..=5).collect();
+    ///
+    /// let rest = d.split_at(2);
+    /// assert_eq!(vec![&1, &2], d.iter_front().collect::<Vec<_>>());
+    /// assert_eq!(vec![&3, &4, &5], rest.iter_front().collect::<Vec<_>>());
+    /// ```
+    pub fn split_at(&mut self, n: usize) -> Deque<T> {
+        let mut rest = Deque::new();
+
+        while self.len() > n {
+            let i = self.pop_back().expect("the deque is known to be non-empty");
+            rest.push_front(i);
+        }
+
+        rest
+    }
+
+    /// Split the deque in two just before the first item, from front to
+    /// back, for which `pred` returns `true`. The items in front of it
+    /// stay in `self`, and that item and the rest are moved, in order,
+    /// into the returned deque. If no item matches, `None` is returned
+    /// and `self` is left unchanged.
+    ///
+    /// As with [`split_at`], tokens for the items that are moved are no
+    /// longer valid.
+    ///
+    /// [`split_at`]: #method.split_at
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use token_deque::Deque;
+    ///
+    /// let mut d: Deque<u8> = (1..=5).collect();
+    ///
+    /// let rest = d.split_when(|i| *i > 3).unwrap();
+    /// assert_eq!(vec![&1, &2, &3], d.iter_front().collect::<Vec<_>>());
+    /// assert_eq!(vec![&4, &5], rest.iter_front().collect::<Vec<_>>());
+    /// ```
+    pub fn split_when<F: FnMut(&T) -> bool>(&mut self, pred: F) -> Option<Deque<T>> {
+        let n = self.iter_front().position(pred)?;
+        Some(self.split_at(n))
+    }
+
+    /// Get a token for the item at position `n`, counting from the
+    /// front. If `n` is at least `len()`, `None` is returned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use token_deque::Deque;
+    ///
+    /// let d: Deque<u8> = (1..=3).collect();
+    ///
+    /// let tok = d.nth_token(1).unwrap();
+    /// assert_eq!(Some(&2), d.get(&tok));
+    /// assert_eq!(None, d.nth_token(3));
+    /// ```
+    pub fn nth_token(&self, n: usize) -> Option<Token> {
+        if n < self.len() {
+            Some(self.token_at(self.nth_index(n)))
+        } else {
+            None
+        }
+    }
+
+    /// Insert `data` before the item currently at position `n`,
+    /// counting from the front. If `n` is zero, `data` becomes the new
+    /// front, and if `n` is at least `len()`, `data` becomes the new
+    /// back. Returns a token for `data`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use token_deque::Deque;
+    ///
+    /// let mut d: Deque<u8> = (1..=3).collect();
+    ///
+    /// let tok = d.insert_at(1, 10);
+    /// assert_eq!(vec![&1, &10, &2, &3], d.iter_front().collect::<Vec<_>>());
+    /// assert_eq!(Some(&10), d.get(&tok));
+    /// ```
+    pub fn insert_at(&mut self, n: usize, data: T) -> Token {
+        if 0 == n {
+            return self.push_front(data);
+        } else if n >= self.len() {
+            return self.push_back(data);
+        }
+
+        let back = self.nth_index(n);
+        let front = self.slots[back]
+            .get_used()
+            .expect("self.slots[back] should always be a used slot")
+            .front();
+
+        let (ix, generation) = self.allocate(front, back, data);
+        self.link_between(ix, front, back);
+
+        Token { ix, generation }
+    }
+
+    /// Remove the item at position `n`, counting from the front, and
+    /// return it. If `n` is at least `len()`, `None` is returned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use token_deque::Deque;
+    ///
+    /// let mut d: Deque<u8> = (1..=3).collect();
+    ///
+    /// assert_eq!(Some(2), d.remove_at(1));
+    /// assert_eq!(None, d.remove_at(2));
+    /// assert_eq!(vec![&1, &3], d.iter_front().collect::<Vec<_>>());
+    /// ```
+    pub fn remove_at(&mut self, n: usize) -> Option<T> {
+        if n < self.len() {
+            let ix = self.nth_index(n);
+            Some(self.remove_unchecked(ix))
+        } else {
+            None
+        }
+    }
+
+    /// Reverse the order of the items from `start` through `end`,
+    /// inclusive. Only links are rewritten, so all tokens remain
+    /// valid. Returns `false` if either token is no longer valid, or
+    /// if `start` comes after `end` in the deque.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use token_deque::Deque;
+    ///
+    /// let mut d: Deque<u8> = Deque::new();
+    ///
+    /// d.push_back(1);
+    /// let start = d.push_back(2);
+    /// d.push_back(3);
+    /// let end = d.push_back(4);
+    /// d.push_back(5);
+    ///
+    /// assert!(d.reverse_range(&start, &end));
+    /// assert_eq!(vec![&1, &4, &3, &2, &5], d.iter_front().collect::<Vec<_>>());
+    /// ```
+    pub fn reverse_range(&mut self, start: &Token, end: &Token) -> bool {
+        let (start, end) = match (self.index_of(start), self.index_of(end)) {
+            (Some(start), Some(end)) if self.is_ordered(start, end) => (start, end),
+            _ => return false,
+        };
+
+        let outer_front = self.links(start).0;
+        let outer_back = self.links(end).1;
+
+        // Flip the links of every node in the range.
+        let mut ix = start;
+        loop {
+            let u = self.slots[ix]
+                .get_used_mut()
+                .expect("self.slots[ix] should always be a used slot");
+            let (front, back) = (u.front(), u.back());
+            u.set_front(back);
+            u.set_back(front);
+
+            if end == ix {
+                break;
+            }
+            ix = back;
+        }
+
+        // Reattach the reversed range to the nodes around it.
+        self.slots[end]
+            .get_used_mut()
+            .expect("self.slots[end] should always be a used slot")
+            .set_front(outer_front);
+        self.slots[start]
+            .get_used_mut()
+            .expect("self.slots[start] should always be a used slot")
+            .set_back(outer_back);
+        self.set_back_of(outer_front, end);
+        self.set_front_of(outer_back, start);
+
+        true
+    }
+
+    /// Move every item of `other` onto the front of `self`, keeping
+    /// their order, and leave `other` empty. Tokens from `other` are
+    /// not valid for `self`, and the slots the items used are moved
+    /// onto the free list of `other`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use token_deque::Deque;
+    ///
+    /// let mut a: Deque<u8> = vec![3, 4].into_iter().collect();
+    /// let mut b: Deque<u8> = vec![1, 2].into_iter().collect();
+    ///
+    /// a.prepend(&mut b);
+    /// assert_eq!(vec![&1, &2, &3, &4], a.iter_front().collect::<Vec<_>>());
+    /// assert!(b.is_empty());
+    /// ```
+    pub fn prepend(&mut self, other: &mut Deque<T>) {
+        self.reserve(other.len().saturating_sub(self.len_free));
+
+        while let Some(i) = other.pop_back() {
+            self.push_front(i);
+        }
+    }
+
+    /// Keep only the first `n` items of the deque, removing everything
+    /// after them. Removed items have their slots moved onto the free
+    /// list. If the deque holds `n` items or fewer, nothing happens.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use token_deque::Deque;
+    ///
+    /// let mut d: Deque<u8> = (1..=5).collect();
+    ///
+    /// d.keep_first_n(2);
+    /// assert_eq!(vec![&1, &2], d.iter_front().collect::<Vec<_>>());
+    /// ```
+    pub fn keep_first_n(&mut self, n: usize) {
+        while self.len() > n {
+            self.pop_back();
+        }
+    }
+
+    /// Keep only the last `n` items of the deque, removing everything
+    /// before them. Removed items have their slots moved onto the free
+    /// list. If the deque holds `n` items or fewer, nothing happens.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use token_deque::Deque;
+    ///
+    /// let mut d: Deque<u8> = (1..=5).collect();
+    ///
+    /// d.keep_last_n(2);
+    /// assert_eq!(vec![&4, &5], d.iter_front().collect::<Vec<_>>());
+    /// ```
+    pub fn keep_last_n(&mut self, n: usize) {
+        while self.len() > n {
+            self.pop_front();
+        }
+    }
+
+    /// Consume the deque and return its raw parts: the `Vec` of slots
+    /// that backs it and the bookkeeping that goes with it. The slots
+    /// are moved, not copied. The deque can be rebuilt with
+    /// [`from_parts`].
+    ///
+    /// [`from_parts`]: #method.from_parts
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use token_deque::Deque;
+    ///
+    /// let d: Deque<u8> = (1..=3).collect();
+    ///
+    /// let parts = d.into_parts();
+    /// assert_eq!(3, parts.slots.len());
+    /// assert_eq!(3, parts.len_used);
+    /// ```
+    pub fn into_parts(self) -> DequeParts<T> {
+        DequeParts {
+            slots: self.slots,
+            free_list: self.free_list,
+            free_tail: self.free_tail,
+            fifo: self.fifo,
+            front: self.front,
+            back: self.back,
+            next_generation: self.next_generation,
+            len_used: self.len_used,
+            len_free: self.len_free,
+        }
+    }
+
+    /// Rebuild a deque from raw parts, such as those returned by
+    /// [`into_parts`]. The parts are checked with [`validate`] first,
+    /// and an error describing the first problem found is returned if
+    /// they are not consistent. Tokens that were valid for the deque
+    /// the parts came from are valid for the rebuilt deque.
+    ///
+    /// Free slots carry no generation, so if `next_generation` has been
+    /// lowered below the generation of an item that was already
+    /// removed, this cannot be detected. New items may then be given
+    /// the generation of a removed item in its old slot, and stale
+    /// tokens for it will silently match them.
+    ///
+    /// [`into_parts`]: #method.into_parts
+    /// [`validate`]: #method.validate
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use token_deque::Deque;
+    ///
+    /// let mut d = Deque::new();
+    /// let tok = d.push_back(1);
+    ///
+    /// let d = Deque::from_parts(d.into_parts()).unwrap();
+    /// assert_eq!(Some(&1), d.get(&tok));
+    /// ```
+    pub fn from_parts(parts: DequeParts<T>) -> Result<Deque<T>, DequeError> {
+        let l = Deque {
+            free_list: parts.free_list,
+            free_tail: parts.free_tail,
+            fifo: parts.fifo,
+            front: parts.front,
+            back: parts.back,
+            next_generation: parts.next_generation,
+            len_used: parts.len_used,
+            len_free: parts.len_free,
+            slots: parts.slots,
+        };
+
+        l.validate().map_err(DequeError::new)?;
+        Ok(l)
+    }
+
+    /// Remove every item from the deque, moving their slots onto the
+    /// free list. Generations keep counting up, so tokens for the
+    /// removed items will never match items added later.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use token_deque::Deque;
+    ///
+    /// let mut d: Deque<u8> = (1..=3).collect();
+    ///
+    /// d.clear();
+    /// assert!(d.is_empty());
+    /// assert_eq!(3, d.len_freelist());
+    /// ```
+    pub fn clear(&mut self) {
+        while self.pop_front().is_some() {}
+    }
+
+    /// Remove every item from the deque, like [`clear`], and reset the
+    /// next generation to zero. The tokens handed out afterwards are
+    /// deterministic: the same sequence of operations after a reset
+    /// always yields the same tokens. They are not necessarily those a
+    /// fresh deque would hand out, since the cleared slots are reused
+    /// in free list order.
+    ///
+    /// Care is needed: since generations are reused, a token for a
+    /// removed item can match a new item that is later put in the same
+    /// slot, and `get`, `remove`, and friends will act on the new item.
+    /// This is mainly useful for making token values repeatable in
+    /// tests.
+    ///
+    /// [`clear`]: #method.clear
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use token_deque::Deque;
+    ///
+    /// let mut d = Deque::new();
+    /// let before = d.push_back(1);
+    ///
+    /// d.clear_reset_generations();
+    /// let after = d.push_back(2);
+    /// assert_eq!(before, after);
+    /// assert_eq!(Some(&2), d.get(&before));
+    /// ```
+    pub fn clear_reset_generations(&mut self) {
+        self.clear();
+        self.next_generation = 0;
+    }
+
+    /// Take a snapshot of the internal layout of the deque: the
+    /// indices of the front, back, and free list, the number of used
+    /// and free slots, and the index of every used slot from front to
+    /// back.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use token_deque::Deque;
+    ///
+    /// let mut d: Deque<u8> = (1..=3).collect();
+    /// d.pop_front();
+    ///
+    /// let layout = d.debug_layout();
+    /// assert_eq!(vec![1, 2], layout.chain);
+    /// assert_eq!(0, layout.free_list);
+    /// ```
+    pub fn debug_layout(&self) -> DebugLayout {
+        let mut chain = Vec::with_capacity(self.len());
+        let mut ix = self.front;
+        while usize::MAX != ix {
+            chain.push(ix);
+            ix = self.links(ix).1;
+        }
+
+        DebugLayout {
+            front: self.front,
+            back: self.back,
+            free_list: self.free_list,
+            len_used: self.len_used,
+            len_free: self.len_free,
+            chain,
+        }
+    }
+
+    /// Check the internal consistency of the deque. This walks the
+    /// deque from front to back, from back to front, and along the
+    /// free list, and makes sure that every slot is accounted for
+    /// exactly once. A description of the first problem found is
+    /// returned as an error. This is intended for use in tests and
+    /// fuzzing; a deque should never fail validation.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use token_deque::Deque;
+    ///
+    /// let mut d: Deque<u8> = (1..=4).collect();
+    /// d.pop_front();
+    ///
+    /// assert_eq!(Ok(()), d.validate());
+    /// ```
+    pub fn validate(&self) -> Result<(), String> {
+        let empty = 0 == self.len_used;
+        if empty != (usize::MAX == self.front) || empty != (usize::MAX == self.back) {
+            return Err(format!(
+                "len is {} but front is {} and back is {}",
+                self.len_used, self.front, self.back
+            ));
+        }
+
+        let mut seen = vec![false; self.slots.len()];
+
+        // Walk from front to back, checking each front link as we go.
+        let mut count = 0;
+        let mut prev = usize::MAX;
+        let mut ix = self.front;
+        while usize::MAX != ix {
+            if ix >= self.slots.len() {
+                return Err(format!("used index {} is out of bounds", ix));
+            }
+            if seen[ix] {
+                return Err(format!("used index {} was visited twice", ix));
+            }
+            seen[ix] = true;
+
+            let u = self.slots[ix]
+                .get_used()
+                .ok_or_else(|| format!("index {} is in the deque but is not used", ix))?;
+            if prev != u.front() {
+                return Err(format!(
+                    "index {} has front {} but is preceded by {}",
+                    ix,
+                    u.front(),
+                    prev
+                ));
+            }
+
+            if u.generation() >= self.next_generation {
+                return Err(format!(
+                    "index {} has generation {} but next generation is {}",
+                    ix,
+                    u.generation(),
+                    self.next_generation
+                ));
+            }
+
+            count += 1;
+            prev = ix;
+            ix = u.back();
+        }
+        if prev != self.back {
+            return Err(format!(
+                "walk from the front ended at {} but back is {}",
+                prev, self.back
+            ));
+        }
+        if count != self.len_used {
+            return Err(format!(
+                "walk from the front visited {} items but len is {}",
+                count, self.len_used
+            ));
+        }
+
+        // Walk from back to front. The front links were checked above,
+        // so this only needs to agree on the count.
+        let mut count = 0;
+        let mut ix = self.back;
+        while usize::MAX != ix && count <= self.len_used {
+            count += 1;
+            ix = self.slots[ix]
+                .get_used()
+                .expect("self.slots[ix] was checked to be used")
+                .front();
+        }
+        if count != self.len_used {
+            return Err(format!(
+                "walk from the back visited {} items but len is {}",
+                count, self.len_used
+            ));
+        }
+
+        // Walk the free list.
+        let mut count = 0;
+        let mut last = usize::MAX;
+        let mut ix = self.free_list;
+        while usize::MAX != ix {
+            if ix >= self.slots.len() {
+                return Err(format!("free index {} is out of bounds", ix));
+            }
+            if seen[ix] {
+                return Err(format!("free index {} was already visited", ix));
+            }
+            seen[ix] = true;
+
+            let f = self.slots[ix]
+                .get_free()
+                .ok_or_else(|| format!("index {} is on the free list but is not free", ix))?;
+
+            count += 1;
+            last = ix;
+            ix = f.next();
+        }
+        if last != self.free_tail {
+            return Err(format!(
+                "free list ended at {} but free tail is {}",
+                last, self.free_tail
+            ));
+        }
+        if count != self.len_free {
+            return Err(format!(
+                "free list has {} items but free len is {}",
+                count, self.len_free
+            ));
+        }
+
+        if let Some(ix) = seen.iter().position(|s| !s) {
+            return Err(format!("index {} is neither used nor free", ix));
+        }
+
+        Ok(())
+    }
+
+    /// A draining iterator that removes items from the front of the
+    /// deque for as long as `pred` returns `true`. The first item for
+    /// which `pred` returns `false` is left in place. Only the items
+    /// that are actually yielded are removed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use token_deque::Deque;
+    ///
+    /// let mut d: Deque<u8> = vec![1, 2, 5, 1].into_iter().collect();
+    ///
+    /// let v: Vec<u8> = d.drain_front_while(|i| *i < 3).collect();
+    /// assert_eq!(vec![1, 2], v);
+    /// assert_eq!(vec![&5, &1], d.iter_front().collect::<Vec<_>>());
+    /// ```
+    pub fn drain_front_while<F: FnMut(&T) -> bool>(
+        &mut self,
+        pred: F,
+    ) -> DrainFrontWhile<'_, T, F> {
+        DrainFrontWhile::new(self, pred)
+    }
+
+    /// A draining iterator that removes items from the back of the
+    /// deque for as long as `pred` returns `true`. The first item for
+    /// which `pred` returns `false` is left in place. Only the items
+    /// that are actually yielded are removed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use token_deque::Deque;
+    ///
+    /// let mut d: Deque<u8> = vec![1, 5, 2, 1].into_iter().collect();
+    ///
+    /// let v: Vec<u8> = d.drain_back_while(|i| *i < 3).collect();
+    /// assert_eq!(vec![1, 2], v);
+    /// assert_eq!(vec![&1, &5], d.iter_front().collect::<Vec<_>>());
+    /// ```
+    pub fn drain_back_while<F: FnMut(&T) -> bool>(&mut self, pred: F) -> DrainBackWhile<'_, T, F> {
+        DrainBackWhile::new(self, pred)
+    }
+
+    /// Remove and drop items from the front of the deque for as long
+    /// as `pred` returns `true` for the front item, and return how many
+    /// were removed. Unlike [`drain_front_while`], the items are not
+    /// yielded.
+    ///
+    /// [`drain_front_while`]: #method.drain_front_while
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use token_deque::Deque;
+    ///
+    /// let mut d: Deque<u8> = vec![0, 0, 3, 0].into_iter().collect();
+    ///
+    /// assert_eq!(2, d.drop_front_while(|i| 0 == *i));
+    /// assert_eq!(vec![&3, &0], d.iter_front().collect::<Vec<_>>());
+    /// ```
+    pub fn drop_front_while<F: FnMut(&T) -> bool>(&mut self, mut pred: F) -> usize {
+        let mut count = 0;
+        while self.get_front().is_some_and(&mut pred) {
+            self.pop_front();
+            count += 1;
+        }
+        count
+    }
+
+    /// Count the items for which `pred` returns `true`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use token_deque::Deque;
+    ///
+    /// let d: Deque<u8> = (1..=5).collect();
+    /// assert_eq!(2, d.count_where(|i| 0 == *i % 2));
+    /// ```
+    pub fn count_where<F: FnMut(&T) -> bool>(&self, mut pred: F) -> usize {
+        self.iter_front().filter(|i| pred(i)).count()
+    }
+
+    /// True when `pred` returns `true` for any item. Items are visited
+    /// from front to back, stopping at the first match. An empty deque
+    /// always returns `false`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use token_deque::Deque;
+    ///
+    /// let d: Deque<u8> = (1..=5).collect();
+    /// assert!(d.any(|i| 3 == *i));
+    /// assert!(!d.any(|i| 6 == *i));
+    /// ```
+    pub fn any<F: FnMut(&T) -> bool>(&self, pred: F) -> bool {
+        self.iter_front().any(pred)
+    }
+
+    /// True when `pred` returns `true` for every item. Items are
+    /// visited from front to back, stopping at the first failure. An
+    /// empty deque always returns `true`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use token_deque::Deque;
+    ///
+    /// let d: Deque<u8> = (1..=5).collect();
+    /// assert!(d.all(|i| *i > 0));
+    /// assert!(!d.all(|i| *i > 1));
+    /// ```
+    pub fn all<F: FnMut(&T) -> bool>(&self, pred: F) -> bool {
+        self.iter_front().all(pred)
+    }
+
+    /// Find the first item, from front to back, for which `pred`
+    /// returns `true`, and return its token. If no item matches,
+    /// `None` is returned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use token_deque::Deque;
+    ///
+    /// let mut d: Deque<u8> = (1..=5).collect();
+    ///
+    /// let tok = d.find_token(|i| 3 == *i).unwrap();
+    /// assert_eq!(Some(3), d.remove(&tok));
+    /// ```
+    pub fn find_token<F: FnMut(&T) -> bool>(&self, mut pred: F) -> Option<Token> {
+        let mut ix = self.front;
+
+        while usize::MAX != ix {
+            let u = self.slots[ix]
+                .get_used()
+                .expect("self.slots[ix] should always be a used slot");
+            if pred(u.data()) {
+                return Some(self.token_at(ix));
+            }
+            ix = u.back();
+        }
+
+        None
+    }
+
+    /// Find the first item, from front to back, for which `pred`
+    /// returns `true`, and return its token. This is the same as
+    /// [`find_token`], and pairs with [`last_token_where`].
+    ///
+    /// [`find_token`]: #method.find_token
+    /// [`last_token_where`]: #method.last_token_where
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use token_deque::Deque;
+    ///
+    /// let d: Deque<u8> = (1..=4).collect();
+    ///
+    /// let tok = d.first_token_where(|i| 0 == i % 2).unwrap();
+    /// assert_eq!(Some(&2), d.get(&tok));
+    /// ```
+    pub fn first_token_where<F: FnMut(&T) -> bool>(&self, pred: F) -> Option<Token> {
+        self.find_token(pred)
+    }
+
+    /// Find the last item for which `pred` returns `true`, walking the
+    /// deque from back to front, and return its token. If no item
+    /// matches, `None` is returned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use token_deque::Deque;
+    ///
+    /// let d: Deque<u8> = (1..=4).collect();
+    ///
+    /// let tok = d.last_token_where(|i| 0 == i % 2).unwrap();
+    /// assert_eq!(Some(&4), d.get(&tok));
+    /// ```
+    pub fn last_token_where<F: FnMut(&T) -> bool>(&self, mut pred: F) -> Option<Token> {
+        let mut ix = self.back;
+
+        while usize::MAX != ix {
+            let u = self.slots[ix]
+                .get_used()
+                .expect("self.slots[ix] should always be a used slot");
+            if pred(u.data()) {
+                return Some(self.token_at(ix));
+            }
+            ix = u.front();
+        }
+
+        None
+    }
+
+    /// Retain only the items for which `pred` returns `true`, like
+    /// [`retain`], but return the removed items in front to back
+    /// order rather than dropping them.
+    ///
+    /// [`retain`]: #method.retain
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use token_deque::Deque;
+    ///
+    /// let mut d: Deque<u8> = (1..=4).collect();
+    ///
+    /// let removed = d.retain_collect(|i| 1 == *i % 2);
+    /// assert_eq!(vec![2, 4], removed);
+    /// assert_eq!(vec![&1, &3], d.iter_front().collect::<Vec<_>>());
+    /// ```
+    pub fn retain_collect<F: FnMut(&T) -> bool>(&mut self, mut pred: F) -> Vec<T> {
+        self.extract_matching(|i| !pred(i))
+            .into_iter()
+            .map(|(_, v)| v)
+            .collect()
+    }
+
+    /// Exchange the position of the item associated with `token` with
+    /// the item just behind it. Only links are rewritten, so both
+    /// tokens remain valid. Returns `false` if the item has already
+    /// been removed, or if it is at the back of the deque.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use token_deque::Deque;
+    ///
+    /// let mut d = Deque::new();
+    /// let a = d.push_back(1);
+    /// d.push_back(2);
+    /// d.push_back(3);
+    ///
+    /// assert!(d.swap_adjacent(&a));
+    /// assert_eq!(vec![&2, &1, &3], d.iter_front().collect::<Vec<_>>());
+    /// ```
+    pub fn swap_adjacent(&mut self, token: &Token) -> bool {
+        let ix = match self.index_of(token) {
+            Some(ix) if self.back != ix => ix,
+            _ => return false,
+        };
+
+        let (_, neighbor) = self.links(ix);
+        let (_, outer_back) = self.links(neighbor);
+        self.unlink(ix);
+        self.link_between(ix, neighbor, outer_back);
+
+        true
+    }
+
+    /// Exchange the positions of the items associated with `a` and
+    /// `b`. The items keep their slots, so both tokens remain valid
+    /// and continue to refer to the same items, which are now in each
+    /// other's former positions. This differs from swapping the values
+    /// themselves, which would leave each token referring to the other
+    /// value. Returns `false` if either token is no longer valid.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use token_deque::Deque;
+    ///
+    /// let mut d = Deque::new();
+    /// let a = d.push_back(1);
+    /// d.push_back(2);
+    /// let b = d.push_back(3);
+    ///
+    /// assert!(d.swap_nodes(&a, &b));
+    /// assert_eq!(vec![&3, &2, &1], d.iter_front().collect::<Vec<_>>());
+    /// assert_eq!(Some(&1), d.get(&a));
+    /// ```
+    pub fn swap_nodes(&mut self, a: &Token, b: &Token) -> bool {
+        let (a, b) = match (self.index_of(a), self.index_of(b)) {
+            (Some(a), Some(b)) => (a, b),
+            _ => return false,
+        };
+
+        if a == b {
+            return true;
+        }
+
+        let (a_front, a_back) = self.links(a);
+        let (b_front, b_back) = self.links(b);
+
+        if a_back == b {
+            // `a` comes right before `b`, so move `a` after `b`.
+            self.unlink(a);
+            self.link_between(a, b, b_back);
+        } else if b_back == a {
+            // `b` comes right before `a`, so move `b` after `a`.
+            self.unlink(b);
+            self.link_between(b, a, a_back);
+        } else {
+            self.unlink(a);
+            self.link_between(a, b_front, b);
+            self.unlink(b);
+            self.link_between(b, a_front, a_back);
+        }
+
+        true
+    }
+
+    /// Collect a token for every item in the deque, from front to
+    /// back.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use token_deque::Deque;
+    ///
+    /// let mut d = Deque::new();
+    /// let a = d.push_back(1);
+    /// let b = d.push_back(2);
+    ///
+    /// assert_eq!(vec![a, b], d.collect_tokens());
+    /// ```
+    pub fn collect_tokens(&self) -> Vec<Token> {
+        let mut tokens = Vec::with_capacity(self.len());
+        let mut ix = self.front;
+
+        while usize::MAX != ix {
+            tokens.push(self.token_at(ix));
+            ix = self.links(ix).1;
+        }
+
+        tokens
+    }
+
+    /// A draining iterator over at most `n` items from the front of the
+    /// deque, moving from front to back. If the deque holds fewer than
+    /// `n` items, all of them are drained. Drained slots are moved onto
+    /// the free list, and all `n` items are removed even if the
+    /// iterator is dropped early.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use token_deque::Deque;
+    ///
+    /// let mut d: Deque<u8> = (1..=5).collect();
+    ///
+    /// let v: Vec<u8> = d.drain_front_n(2).collect();
+    /// assert_eq!(vec![1, 2], v);
+    /// assert_eq!(vec![&3, &4, &5], d.iter_front().collect::<Vec<_>>());
+    /// ```
+    pub fn drain_front_n(&mut self, n: usize) -> DrainFrontN<'_, T> {
+        DrainFrontN::new(self, n)
+    }
+
+    /// A draining iterator over at most `n` items from the back of the
+    /// deque, moving from back to front. If the deque holds fewer than
+    /// `n` items, all of them are drained. Drained slots are moved onto
+    /// the free list, and all `n` items are removed even if the
+    /// iterator is dropped early.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use token_deque::Deque;
+    ///
+    /// let mut d: Deque<u8> = (1..=5).collect();
+    ///
+    /// let v: Vec<u8> = d.drain_back_n(2).collect();
+    /// assert_eq!(vec![5, 4], v);
+    /// assert_eq!(vec![&1, &2, &3], d.iter_front().collect::<Vec<_>>());
+    /// ```
+    pub fn drain_back_n(&mut self, n: usize) -> DrainBackN<'_, T> {
+        DrainBackN::new(self, n)
+    }
+
+    /// Remove up to `n` items from the front of the deque, yielding
+    /// them from front to back. If the deque holds fewer than `n`
+    /// items, all of them are removed. All `n` items are removed even
+    /// if the iterator is dropped early.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use token_deque::Deque;
+    ///
+    /// let mut d: Deque<u8> = (1..=5).collect();
+    ///
+    /// let v: Vec<u8> = d.pop_front_n(2).collect();
+    /// assert_eq!(vec![1, 2], v);
+    /// assert_eq!(vec![&3, &4, &5], d.iter_front().collect::<Vec<_>>());
+    /// ```
+    pub fn pop_front_n(&mut self, n: usize) -> impl Iterator<Item = T> + '_ {
+        self.drain_front_n(n)
+    }
+
+    /// Remove up to `n` items from the back of the deque, yielding
+    /// them from back to front. If the deque holds fewer than `n`
+    /// items, all of them are removed. All `n` items are removed even
+    /// if the iterator is dropped early.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use token_deque::Deque;
+    ///
+    /// let mut d: Deque<u8> = (1..=5).collect();
+    ///
+    /// let v: Vec<u8> = d.pop_back_n(2).collect();
+    /// assert_eq!(vec![5, 4], v);
+    /// assert_eq!(vec![&1, &2, &3], d.iter_front().collect::<Vec<_>>());
+    /// ```
+    pub fn pop_back_n(&mut self, n: usize) -> impl Iterator<Item = T> + '_ {
+        self.drain_back_n(n)
+    }
+
+    /// Collect references to every item in the deque, from front to
+    /// back, into a `Vec` sized to fit exactly.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use token_deque::Deque;
+    ///
+    /// let d: Deque<u8> = (1..=3).collect();
+    /// assert_eq!(vec![&1, &2, &3], d.as_ref_vec());
+    /// ```
+    pub fn as_ref_vec(&self) -> Vec<&T> {
+        let mut v = Vec::with_capacity(self.len());
+        v.extend(self.iter_front());
+        v
+    }
+
+    /// Push each item of `iter` onto the front of the deque, returning
+    /// the tokens for the new items in the order they were inserted.
+    /// Since each item is pushed onto the front, the items end up in
+    /// the deque in reverse order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use token_deque::Deque;
+    ///
+    /// let mut d: Deque<u8> = Deque::new();
+    ///
+    /// let tokens = d.extend_front(vec![1, 2, 3]);
+    /// assert_eq!(vec![&3, &2, &1], d.iter_front().collect::<Vec<_>>());
+    /// assert_eq!(Some(&1), d.get(&tokens[0]));
+    /// ```
+    pub fn extend_front<I: IntoIterator<Item = T>>(&mut self, iter: I) -> Vec<Token> {
+        let iter = iter.into_iter();
+        let mut tokens = Vec::with_capacity(iter.size_hint().0);
+        self.reserve(tokens.capacity().saturating_sub(self.len_free));
+
+        for i in iter {
+            tokens.push(self.push_front(i));
+        }
+
+        tokens
+    }
+
+    /// Push each item of `iter` onto the back of the deque, returning
+    /// the tokens for the new items in the order they were inserted.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use token_deque::Deque;
+    ///
+    /// let mut d: Deque<u8> = Deque::new();
+    ///
+    /// let tokens = d.extend_back(vec![1, 2, 3]);
+    /// assert_eq!(vec![&1, &2, &3], d.iter_front().collect::<Vec<_>>());
+    /// assert_eq!(Some(&1), d.get(&tokens[0]));
+    /// ```
+    pub fn extend_back<I: IntoIterator<Item = T>>(&mut self, iter: I) -> Vec<Token> {
+        let iter = iter.into_iter();
+        let mut tokens = Vec::with_capacity(iter.size_hint().0);
+        self.reserve(tokens.capacity().saturating_sub(self.len_free));
+
+        for i in iter {
+            tokens.push(self.push_back(i));
+        }
+
+        tokens
+    }
+
+    /// Merge two sorted deques into a single sorted deque. Both
+    /// deques must already be sorted in ascending order, or the result
+    /// will not be sorted either. Equal items from `self` come before
+    /// those from `other`. Tokens from either deque are not valid for
+    /// the merged deque.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use token_deque::Deque;
+    ///
+    /// let a: Deque<u8> = vec![1, 3, 5].into_iter().collect();
+    /// let b: Deque<u8> = vec![2, 4, 6].into_iter().collect();
+    ///
+    /// let m = a.merge_sorted(b);
+    /// assert_eq!(vec![&1, &2, &3, &4, &5, &6], m.iter_front().collect::<Vec<_>>());
+    /// ```
+    pub fn merge_sorted(self, other: Deque<T>) -> Deque<T>
+    where
+        T: Ord,
+    {
+        self.merge_sorted_by(other, |a, b| a.cmp(b))
+    }
+
+    /// Merge two deques sorted according to `cmp` into a single
+    /// deque sorted the same way. Both deques must already be sorted,
+    /// or the result will not be sorted either. Equal items from
+    /// `self` come before those from `other`. Tokens from either deque
+    /// are not valid for the merged deque.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use token_deque::Deque;
+    ///
+    /// let a: Deque<u8> = vec![5, 3, 1].into_iter().collect();
+    /// let b: Deque<u8> = vec![6, 4, 2].into_iter().collect();
+    ///
+    /// let m = a.merge_sorted_by(b, |a, b| b.cmp(a));
+    /// assert_eq!(vec![&6, &5, &4, &3, &2, &1], m.iter_front().collect::<Vec<_>>());
+    /// ```
+    pub fn merge_sorted_by<F: FnMut(&T, &T) -> Ordering>(
+        mut self,
+        mut other: Deque<T>,
+        mut cmp: F,
+    ) -> Deque<T> {
+        let mut merged = Deque::with_capacity_exact(self.len() + other.len());
+
+        loop {
+            let take_other = match (self.get_front(), other.get_front()) {
+                (Some(a), Some(b)) => Ordering::Greater == cmp(a, b),
+                (Some(_), None) => false,
+                (None, Some(_)) => true,
+                (None, None) => break,
+            };
+
+            let i = if take_other {
+                other.pop_front()
+            } else {
+                self.pop_front()
+            };
+            merged.push_back(i.expect("the chosen deque is known to be non-empty"));
+        }
+
+        merged
+    }
+
+    /// Combine two deques by alternately taking items from the front
+    /// of `self` and the front of `other`, starting with `self`. Once
+    /// one deque runs out, the rest of the other is appended. Tokens
+    /// from either deque are not valid for the combined deque.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use token_deque::Deque;
+    ///
+    /// let a: Deque<u8> = vec![1, 3, 5].into_iter().collect();
+    /// let b: Deque<u8> = vec![2, 4].into_iter().collect();
+    ///
+    /// let i = a.interleave(b);
+    /// assert_eq!(vec![&1, &2, &3, &4, &5], i.iter_front().collect::<Vec<_>>());
+    /// ```
+    pub fn interleave(mut self, mut other: Deque<T>) -> Deque<T> {
+        let mut combined = Deque::with_capacity_exact(self.len() + other.len());
+
+        loop {
+            match (self.pop_front(), other.pop_front()) {
+                (Some(a), Some(b)) => {
+                    combined.push_back(a);
+                    combined.push_back(b);
+                }
+                (Some(a), None) => {
+                    combined.push_back(a);
+                }
+                (None, Some(b)) => {
+                    combined.push_back(b);
+                }
+                (None, None) => break,
+            }
+        }
+
+        combined
+    }
+
+    /// Remove consecutive items that map to the same key, keeping only
+    /// the first item of each run. Items are visited from front to
+    /// back. Tokens for the kept items remain valid, and the removed
+    /// items have their slots moved onto the free list.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use token_deque::Deque;
+    ///
+    /// let mut d: Deque<u8> = vec![10, 11, 20, 30, 31].into_iter().collect();
+    ///
+    /// d.dedup_by_key(|i| *i / 10);
+    /// assert_eq!(vec![&10, &20, &30], d.iter_front().collect::<Vec<_>>());
+    /// ```
+    pub fn dedup_by_key<K: PartialEq, F: FnMut(&mut T) -> K>(&mut self, mut key: F) {
+        let mut prev: Option<K> = None;
+        let mut ix = self.front;
+
+        while usize::MAX != ix {
+            let u = self.slots[ix]
+                .get_used_mut()
+                .expect("self.slots[ix] should always be a used slot");
+            let next = u.back();
+            let k = key(u.data_mut());
+
+            if prev.as_ref() == Some(&k) {
+                self.remove_unchecked(ix);
+            } else {
+                prev = Some(k);
+            }
+
+            ix = next;
+        }
+    }
+
+    /// Create an iterator that walks the deque from front to back
+    /// `cycles` times in a row. If the deque is empty, or `cycles` is
+    /// zero, the iterator is empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use token_deque::Deque;
+    ///
+    /// let d: Deque<u8> = vec![1, 2].into_iter().collect();
+    ///
+    /// let v: Vec<&u8> = d.iter_cycle(3).collect();
+    /// assert_eq!(vec![&1, &2, &1, &2, &1, &2], v);
+    /// ```
+    pub fn iter_cycle(&self, cycles: usize) -> impl Iterator<Item = &T> + '_ {
+        IterCycle::new(self, cycles)
+    }
+
+    /// Create an iterator over the deque from front to back that
+    /// yields the position of each item, counting from the front, and
+    /// its token along with the item.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use token_deque::Deque;
+    ///
+    /// let d: Deque<u8> = vec![10, 20].into_iter().collect();
+    ///
+    /// for (n, tok, i) in d.iter_front_positioned() {
+    ///     assert_eq!(Some(i), d.get(&tok));
+    ///     assert_eq!(Some(tok), d.nth_token(n));
+    /// }
+    /// ```
+    pub fn iter_front_positioned(&self) -> impl Iterator<Item = (usize, Token, &T)> + '_ {
+        IterPositioned::new(self)
+    }
+
+    /// Create an iterator that walks the deque from front to back and
+    /// yields the items in `Vec`s of `size` references. The last `Vec`
+    /// may hold fewer than `size` references.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `size` is zero.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use token_deque::Deque;
+    ///
+    /// let d: Deque<u8> = (1..=5).collect();
+    ///
+    /// let v: Vec<Vec<&u8>> = d.chunks_front(2).collect();
+    /// assert_eq!(vec![vec![&1, &2], vec![&3, &4], vec![&5]], v);
+    /// ```
+    pub fn chunks_front(&self, size: usize) -> impl Iterator<Item = Vec<&T>> + '_ {
+        Chunks::new(self.iter_front(), size)
+    }
+
+    /// Create an iterator that walks the deque from back to front and
+    /// yields the items in `Vec`s of `size` references. Each `Vec` holds
+    /// its items in back to front order, and the last `Vec`, nearest
+    /// the front, may hold fewer than `size` references.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `size` is zero.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use token_deque::Deque;
+    ///
+    /// let d: Deque<u8> = (1..=5).collect();
+    ///
+    /// let v: Vec<Vec<&u8>> = d.chunks_back(2).collect();
+    /// assert_eq!(vec![vec![&5, &4], vec![&3, &2], vec![&1]], v);
+    /// ```
+    pub fn chunks_back(&self, size: usize) -> impl Iterator<Item = Vec<&T>> + '_ {
+        Chunks::new(self.iter_back(), size)
+    }
+
+    /// Create an iterator that yields the front item of the deque and
+    /// then every `step`th item after it, walking toward the back.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `step` is zero.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use token_deque::Deque;
+    ///
+    /// let d: Deque<u8> = (0..6).collect();
+    ///
+    /// let v: Vec<&u8> = d.step_by_front(2).collect();
+    /// assert_eq!(vec![&0, &2, &4], v);
+    /// ```
+    pub fn step_by_front(&self, step: usize) -> impl Iterator<Item = &T> + '_ {
+        self.iter_front().step_by(step)
+    }
+
+    /// Create an iterator over the deque from the front that stops at
+    /// the first item for which `pred` returns `false`. Unlike
+    /// [`drain_front_while`], nothing is removed from the deque.
+    ///
+    /// [`drain_front_while`]: #method.drain_front_while
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use token_deque::Deque;
+    ///
+    /// let d: Deque<u8> = vec![1, 2, 5, 1].into_iter().collect();
+    ///
+    /// let v: Vec<&u8> = d.iter_while_front(|i| *i < 3).collect();
+    /// assert_eq!(vec![&1, &2], v);
+    /// assert_eq!(4, d.len());
+    /// ```
+    pub fn iter_while_front<'l, F: FnMut(&T) -> bool + 'l>(
+        &'l self,
+        mut pred: F,
+    ) -> impl Iterator<Item = &'l T> + 'l {
+        self.iter_front().take_while(move |i| pred(i))
+    }
+
+    /// Create an iterator over each pair of neighboring items, from
+    /// front to back. A deque of `n` items yields `n - 1` pairs, and an
+    /// empty deque, or one with a single item, yields none.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use token_deque::Deque;
+    ///
+    /// let d: Deque<u8> = (1..=3).collect();
+    ///
+    /// let v: Vec<(&u8, &u8)> = d.pairs_front().collect();
+    /// assert_eq!(vec![(&1, &2), (&2, &3)], v);
+    /// ```
+    pub fn pairs_front(&self) -> impl Iterator<Item = (&T, &T)> + '_ {
+        self.iter_front().zip(self.iter_front().skip(1))
+    }
+
+    /// Create a clone of the deque with its items packed into the
+    /// start of a new `Vec` in front to back order, leaving no free
+    /// slots behind. Since the items move, the clone uses different
+    /// tokens than `self`; the returned map takes each token of `self`
+    /// to the matching token of the clone. `self` is not changed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use token_deque::Deque;
+    ///
+    /// let mut d: Deque<u8> = Deque::new();
+    /// d.push_back(1);
+    /// let tok = d.push_back(2);
+    /// d.pop_front();
+    ///
+    /// let (c, remap) = d.clone_compact();
+    /// assert_eq!(0, c.len_freelist());
+    /// assert_eq!(Some(&2), c.get(&remap[&tok]));
+    /// ```
+    pub fn clone_compact(&self) -> (Deque<T>, HashMap<Token, Token>)
+    where
+        T: Clone,
+    {
+        let mut compact = Deque::new();
+        compact.slots.reserve_exact(self.len());
+        let mut remap = HashMap::with_capacity(self.len());
+
+        let mut ix = self.front;
+        while usize::MAX != ix {
+            let u = self.slots[ix]
+                .get_used()
+                .expect("self.slots[ix] should always be a used slot");
+            let token = compact.push_back(u.data().clone());
+            remap.insert(self.token_at(ix), token);
+            ix = u.back();
+        }
+
+        (compact, remap)
+    }
+
+    /// Retain only the items for which `f` returns `true`, and pack the
+    /// retained items into the start of a new `Vec` in front to back
+    /// order, leaving no free slots behind. This is done in a single
+    /// walk of the deque. Since the retained items move, the returned
+    /// map takes each of their old tokens to their new tokens. Retained
+    /// items keep their generations, so old tokens for removed items
+    /// can not match the new layout.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use token_deque::Deque;
+    ///
+    /// let mut d = Deque::new();
+    /// d.push_back(1);
+    /// let tok = d.push_back(2);
+    ///
+    /// let remap = d.retain_compact(|i| 0 == i % 2);
+    /// assert_eq!(0, d.len_freelist());
+    /// assert_eq!(1, d.capacity());
+    /// assert_eq!(Some(&2), d.get(&remap[&tok]));
+    /// ```
+    pub fn retain_compact<F: FnMut(&T) -> bool>(&mut self, mut f: F) -> HashMap<Token, Token> {
+        let mut old = std::mem::take(&mut self.slots);
+        let mut ix = self.front;
+
+        self.slots.reserve_exact(self.len());
+        self.free_list = usize::MAX;
+        self.free_tail = usize::MAX;
+        self.front = usize::MAX;
+        self.back = usize::MAX;
+        self.len_used = 0;
+        self.len_free = 0;
+
+        let mut remap = HashMap::new();
+        while usize::MAX != ix {
+            let u = std::mem::replace(&mut old[ix], Slot::new_free(usize::MAX))
+                .into_used()
+                .expect("old[ix] should always be a used slot");
+            let generation = u.generation();
+            let (_, data, next) = u.take();
+
+            if f(&data) {
+                let new_ix = self.slots.len();
+                self.slots
+                    .push(Slot::new_used(self.back, usize::MAX, generation, data));
+                self.set_back_of(self.back, new_ix);
+                self.back = new_ix;
+                self.len_used += 1;
+                remap.insert(
+                    Token { ix, generation },
+                    Token {
+                        ix: new_ix,
+                        generation,
+                    },
+                );
+            }
+
+            ix = next;
+        }
+
+        self.slots.shrink_to_fit();
+        remap
+    }
+
+    /// Look up `old` in `remap`, such as the map returned by
+    /// [`clone_compact`] or [`retain_compact`], and return the token
+    /// that replaces it. If the token has no entry, because its item
+    /// did not survive, `None` is returned.
+    ///
+    /// This doesn't use the item type, so it has to be named at the
+    /// call site. [`Token::remap`] does the same thing without it.
+    ///
+    /// [`clone_compact`]: #method.clone_compact
+    /// [`retain_compact`]: #method.retain_compact
+    /// [`Token::remap`]: struct.Token.html#method.remap
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use token_deque::Deque;
+    ///
+    /// let mut d = Deque::new();
+    /// let a = d.push_back(1u8);
+    /// let b = d.push_back(2);
+    /// d.remove(&a);
+    ///
+    /// let (compact, remap) = d.clone_compact();
+    /// assert_eq!(None, Deque::<u8>::remap_token(&remap, &a));
+    /// let b = Deque::<u8>::remap_token(&remap, &b).unwrap();
+    /// assert_eq!(Some(&2), compact.get(&b));
+    /// ```
+    pub fn remap_token(remap: &HashMap<Token, Token>, old: &Token) -> Option<Token> {
+        remap.get(old).copied()
+    }
+
+    /// Replace each of `tokens` with the token it maps to in `remap`,
+    /// in place, and return the number of tokens that survived. Since
+    /// a slice can't shrink, the survivors are moved to the start of
+    /// `tokens` in their original order, and the tokens after them
+    /// are left in an unspecified order. Tokens with no entry in
+    /// `remap` are among those after the survivors.
+    ///
+    /// Like [`remap_token`], this doesn't use the item type, so it has
+    /// to be named at the call site. [`Token::remap_in_place`] does the
+    /// same thing for a `Vec`, truncating it to the survivors.
+    ///
+    /// [`remap_token`]: #method.remap_token
+    /// [`Token::remap_in_place`]: struct.Token.html#method.remap_in_place
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use token_deque::Deque;
+    ///
+    /// let mut d = Deque::new();
+    /// let mut toks = [d.push_back(1u8), d.push_back(2), d.push_back(3)];
+    /// d.remove(&toks[1]);
+    ///
+    /// let (compact, remap) = d.clone_compact();
+    /// let n = Deque::<u8>::remap_tokens_in_place(&remap, &mut toks);
+    /// assert_eq!(2, n);
+    /// assert_eq!(Some(&1), compact.get(&toks[0]));
+    /// assert_eq!(Some(&3), compact.get(&toks[1]));
+    /// ```
+    pub fn remap_tokens_in_place(remap: &HashMap<Token, Token>, tokens: &mut [Token]) -> usize {
+        let mut kept = 0;
+
+        for i in 0..tokens.len() {
+            if let Some(new) = Self::remap_token(remap, &tokens[i]) {
+                tokens.swap(kept, i);
+                tokens[kept] = new;
+                kept += 1;
+            }
+        }
+
+        kept
+    }
+
+    /// Retain only the items for which `f` returns `true`, passing
+    /// each item to `f` along with its token. Items are visited from
+    /// front to back, and removed items have their slots moved onto
+    /// the free list. Retained items keep their tokens.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use token_deque::Deque;
+    ///
+    /// let mut d = Deque::new();
+    /// d.push_back(1);
+    /// let keep = d.push_back(2);
+    /// d.push_back(3);
+    ///
+    /// d.retain_tokens(|t, _| *t == keep);
+    /// assert_eq!(vec![&2], d.iter_front().collect::<Vec<_>>());
+    /// ```
+    pub fn retain_tokens<F: FnMut(&Token, &T) -> bool>(&mut self, mut f: F) {
+        let mut ix = self.front;
+
+        while usize::MAX != ix {
+            let token = self.token_at(ix);
+            let u = self.slots[ix]
+                .get_used()
+                .expect("self.slots[ix] should always be a used slot");
+            let next = u.back();
+
+            if !f(&token, u.data()) {
+                self.remove_unchecked(ix);
+            }
+
+            ix = next;
+        }
+    }
+
+    /// Move every item of the deque, from front to back, onto the end
+    /// of `sink`. The deque is left empty, with every drained slot on
+    /// the free list.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use token_deque::Deque;
+    ///
+    /// let mut d: Deque<u8> = (1..=3).collect();
+    /// let mut v = vec![0];
+    ///
+    /// d.drain_all_into(&mut v);
+    /// assert_eq!(vec![0, 1, 2, 3], v);
+    /// assert!(d.is_empty());
+    /// ```
+    pub fn drain_all_into(&mut self, sink: &mut Vec<T>) {
+        sink.reserve(self.len());
+        sink.extend(self.drain_front());
+    }
+
+    /// Move every item of the deque, from back to front, onto the end
+    /// of `sink`. The deque is left empty, with every drained slot on
+    /// the free list.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use token_deque::Deque;
+    ///
+    /// let mut d: Deque<u8> = (1..=3).collect();
+    /// let mut v = vec![0];
+    ///
+    /// d.drain_back_into(&mut v);
+    /// assert_eq!(vec![0, 3, 2, 1], v);
+    /// assert!(d.is_empty());
+    /// ```
+    pub fn drain_back_into(&mut self, sink: &mut Vec<T>) {
+        sink.reserve(self.len());
+        sink.extend(self.drain_back());
+    }
+
+    /// Consume the deque and return its items in a `Vec`, sorted in
+    /// ascending order. The sort is stable, so equal items keep their
+    /// front to back order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use token_deque::Deque;
+    ///
+    /// let d: Deque<u8> = vec![3, 1, 2].into_iter().collect();
+    ///
+    /// assert_eq!(vec![1, 2, 3], d.into_sorted_vec());
+    /// ```
+    pub fn into_sorted_vec(self) -> Vec<T>
+    where
+        T: Ord,
+    {
+        self.into_sorted_vec_by(|a, b| a.cmp(b))
+    }
+
+    /// Consume the deque and return its items in a `Vec`, sorted with
+    /// the comparator `compare`. The sort is stable, so equal items
+    /// keep their front to back order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use token_deque::Deque;
+    ///
+    /// let d: Deque<u8> = vec![3, 1, 2].into_iter().collect();
+    ///
+    /// assert_eq!(vec![3, 2, 1], d.into_sorted_vec_by(|a, b| b.cmp(a)));
+    /// ```
+    pub fn into_sorted_vec_by<F: FnMut(&T, &T) -> Ordering>(mut self, compare: F) -> Vec<T> {
+        let mut v = Vec::new();
+        self.drain_all_into(&mut v);
+        v.sort_by(compare);
+        v
+    }
+
+    /// Returns `true` if `self` and `other` have the same length and
+    /// `eq` returns `true` for each pair of items, walking both from
+    /// front to back. The walk stops at the first pair that is not
+    /// equal.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use token_deque::Deque;
+    ///
+    /// let a: Deque<u8> = (1..=3).collect();
+    /// let b: Deque<u32> = (1..=3).collect();
+    ///
+    /// assert!(a.eq_by(&b, |x, y| u32::from(*x) == *y));
+    /// ```
+    pub fn eq_by<U, F: FnMut(&T, &U) -> bool>(&self, other: &Deque<U>, mut eq: F) -> bool {
+        self.len() == other.len()
+            && self
+                .iter_front()
+                .zip(other.iter_front())
+                .all(|(a, b)| eq(a, b))
+    }
+
+    /// Create a new deque by calling `f` on each pair of items from
+    /// `self` and `other`, walking both from front to back. The new
+    /// deque stops at the end of the shorter of the two.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use token_deque::Deque;
+    ///
+    /// let a: Deque<u8> = (1..=3).collect();
+    /// let b: Deque<u8> = vec![10, 20, 30].into_iter().collect();
+    ///
+    /// let c = a.zip_map(&b, |x, y| x + y);
+    /// assert_eq!(vec![&11, &22, &33], c.iter_front().collect::<Vec<_>>());
+    /// ```
+    pub fn zip_map<U, V, F: FnMut(&T, &U) -> V>(&self, other: &Deque<U>, mut f: F) -> Deque<V> {
+        self.iter_front()
+            .zip(other.iter_front())
+            .map(|(a, b)| f(a, b))
+            .collect()
+    }
+
+    /// Find the position, counted from the front, of the first item
+    /// equal to `value`. If no item is equal, `None` is returned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use token_deque::Deque;
+    ///
+    /// let d: Deque<u8> = vec![1, 2, 3, 2, 1].into_iter().collect();
+    ///
+    /// assert_eq!(Some(1), d.position(&2));
+    /// assert_eq!(None, d.position(&4));
+    /// ```
+    pub fn position(&self, value: &T) -> Option<usize>
+    where
+        T: PartialEq,
+    {
+        self.iter_front().position(|i| i == value)
+    }
+
+    /// Find the position, counted from the front, of the last item
+    /// equal to `value`. The deque is walked from back to front. If no
+    /// item is equal, `None` is returned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use token_deque::Deque;
+    ///
+    /// let d: Deque<u8> = vec![1, 2, 3, 2, 1].into_iter().collect();
+    ///
+    /// assert_eq!(Some(3), d.rposition(&2));
+    /// assert_eq!(None, d.rposition(&4));
+    /// ```
+    pub fn rposition(&self, value: &T) -> Option<usize>
+    where
+        T: PartialEq,
+    {
+        self.iter_back()
+            .position(|i| i == value)
+            .map(|n| self.len() - 1 - n)
+    }
+
+    /// Create a new deque holding the items of each of `parts`, in
+    /// order, with the items of each part kept front to back. Space
+    /// for all of the items is reserved up front. Tokens from the
+    /// parts are not valid for the new deque.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use token_deque::Deque;
+    ///
+    /// let a: Deque<u8> = (1..=2).collect();
+    /// let b: Deque<u8> = (3..=4).collect();
+    ///
+    /// let d = Deque::concat(vec![a, b]);
+    /// assert_eq!(vec![&1, &2, &3, &4], d.iter_front().collect::<Vec<_>>());
+    /// ```
+    pub fn concat(parts: Vec<Deque<T>>) -> Deque<T> {
+        let mut l = Self::new();
+        l.reserve(parts.iter().map(Deque::len).sum());
+
+        for mut part in parts {
+            for i in part.drain_front() {
+                l.push_back(i);
+            }
+        }
+
+        l
+    }
+
+    /// Fold the items of the deque, from front to back, into an
+    /// accumulator starting at `init`. The fold ends at the first item
+    /// for which `f` returns an error, and that error is returned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use token_deque::Deque;
+    ///
+    /// let d: Deque<u8> = (1..=4).collect();
+    ///
+    /// let sum = d.try_fold_front(0u8, |acc, i| acc.checked_add(*i).ok_or(*i));
+    /// assert_eq!(Ok(10), sum);
+    /// ```
+    pub fn try_fold_front<B, E, F: FnMut(B, &T) -> Result<B, E>>(
+        &self,
+        init: B,
+        f: F,
+    ) -> Result<B, E> {
+        self.iter_front().try_fold(init, f)
+    }
+
+    /// Fold the items of the deque, from back to front, into an
+    /// accumulator starting at `init`. The fold ends at the first item
+    /// for which `f` returns an error, and that error is returned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use token_deque::Deque;
+    ///
+    /// let d: Deque<u8> = (1..=4).collect();
+    ///
+    /// let digits = d.try_fold_back(0u32, |acc, i| Ok::<_, ()>(acc * 10 + u32::from(*i)));
+    /// assert_eq!(Ok(4321), digits);
+    /// ```
+    pub fn try_fold_back<B, E, F: FnMut(B, &T) -> Result<B, E>>(
+        &self,
+        init: B,
+        f: F,
+    ) -> Result<B, E> {
+        self.iter_back().try_fold(init, f)
+    }
+
+    /// Returns `true` if `in_order` returns `true` for every pair of
+    /// neighboring items, walking from front to back. The walk stops at
+    /// the first pair that is out of order. An empty deque, or one with
+    /// a single item, is sorted.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use token_deque::Deque;
+    ///
+    /// let d: Deque<u8> = vec![3, 2, 1].into_iter().collect();
+    ///
+    /// assert!(d.is_sorted_by(|a, b| a >= b));
+    /// assert!(!d.is_sorted_by(|a, b| a <= b));
+    /// ```
+    pub fn is_sorted_by<F: FnMut(&T, &T) -> bool>(&self, mut in_order: F) -> bool {
+        let mut iter = self.iter_front();
+        let mut prev = match iter.next() {
+            Some(i) => i,
+            None => return true,
+        };
+
+        for i in iter {
+            if !in_order(prev, i) {
+                return false;
+            }
+            prev = i;
+        }
+
+        true
+    }
+
+    /// Returns `true` if the items of the deque are in ascending order
+    /// from front to back. An empty deque, or one with a single item,
+    /// is sorted.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use token_deque::Deque;
+    ///
+    /// let d: Deque<u8> = vec![1, 2, 2, 3].into_iter().collect();
+    ///
+    /// assert!(d.is_sorted());
+    /// ```
+    pub fn is_sorted(&self) -> bool
+    where
+        T: PartialOrd,
+    {
+        self.is_sorted_by(|a, b| a <= b)
+    }
+
+    /// Remove every item for which `pred` returns `true`, walking from
+    /// front to back, and return each removed item along with the token
+    /// that referred to it. The returned tokens are stale, but can be
+    /// used to clean up a side index. Items that are kept keep their
+    /// tokens.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use token_deque::Deque;
+    ///
+    /// let mut d = Deque::new();
+    /// d.push_back(1);
+    /// let tok = d.push_back(2);
+    /// d.push_back(3);
+    ///
+    /// assert_eq!(vec![(tok, 2)], d.extract_matching(|i| 0 == i % 2));
+    /// assert_eq!(vec![&1, &3], d.iter_front().collect::<Vec<_>>());
+    /// ```
+    pub fn extract_matching<F: FnMut(&T) -> bool>(&mut self, mut pred: F) -> Vec<(Token, T)> {
+        let mut extracted = Vec::new();
+        let mut ix = self.front;
+
+        while usize::MAX != ix {
+            let u = self.slots[ix]
+                .get_used()
+                .expect("self.slots[ix] should always be a used slot");
+            let next = u.back();
+
+            if pred(u.data()) {
+                let token = self.token_at(ix);
+                extracted.push((token, self.remove_unchecked(ix)));
+            }
+
+            ix = next;
+        }
+
+        extracted
+    }
+
+    /// Move the front item of `other` onto the back of `self`, and
+    /// return its new token. If `other` is empty, `None` is returned.
+    /// The item's token in `other` is no longer valid.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use token_deque::Deque;
+    ///
+    /// let mut a: Deque<u8> = (1..=2).collect();
+    /// let mut b: Deque<u8> = (3..=4).collect();
+    ///
+    /// let tok = a.steal_front_from(&mut b).unwrap();
+    /// assert_eq!(Some(&3), a.get(&tok));
+    /// assert_eq!(vec![&1, &2, &3], a.iter_front().collect::<Vec<_>>());
+    /// assert_eq!(vec![&4], b.iter_front().collect::<Vec<_>>());
+    /// ```
+    pub fn steal_front_from(&mut self, other: &mut Deque<T>) -> Option<Token> {
+        other.pop_front().map(|i| self.push_back(i))
+    }
+
+    /// Move the back item of `other` onto the front of `self`, and
+    /// return its new token. If `other` is empty, `None` is returned.
+    /// The item's token in `other` is no longer valid.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use token_deque::Deque;
+    ///
+    /// let mut a: Deque<u8> = (3..=4).collect();
+    /// let mut b: Deque<u8> = (1..=2).collect();
+    ///
+    /// let tok = a.steal_back_from(&mut b).unwrap();
+    /// assert_eq!(Some(&2), a.get(&tok));
+    /// assert_eq!(vec![&2, &3, &4], a.iter_front().collect::<Vec<_>>());
+    /// assert_eq!(vec![&1], b.iter_front().collect::<Vec<_>>());
+    /// ```
+    pub fn steal_back_from(&mut self, other: &mut Deque<T>) -> Option<Token> {
+        other.pop_back().map(|i| self.push_front(i))
+    }
+
+    /// Find the smallest item according to `compare`, and return its
+    /// token. If several items are equally small, the one nearest the
+    /// front is chosen. If the deque is empty, `None` is returned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use token_deque::Deque;
+    ///
+    /// let mut d: Deque<u8> = vec![3, 7, 2, 7].into_iter().collect();
+    ///
+    /// let tok = d.min_by(|a, b| a.cmp(b)).unwrap();
+    /// assert_eq!(Some(2), d.remove(&tok));
+    /// ```
+    pub fn min_by<F: FnMut(&T, &T) -> Ordering>(&self, mut compare: F) -> Option<Token> {
+        self.best_by(|a, b| compare(a, b) == Ordering::Less)
+    }
+
+    /// Find the largest item according to `compare`, and return its
+    /// token. If several items are equally large, the one nearest the
+    /// front is chosen. If the deque is empty, `None` is returned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use token_deque::Deque;
+    ///
+    /// let mut d: Deque<u8> = vec![3, 7, 2, 7].into_iter().collect();
+    ///
+    /// let tok = d.max_by(|a, b| a.cmp(b)).unwrap();
+    /// assert_eq!(Some(7), d.remove(&tok));
+    /// assert_eq!(vec![&3, &2, &7], d.iter_front().collect::<Vec<_>>());
+    /// ```
+    pub fn max_by<F: FnMut(&T, &T) -> Ordering>(&self, mut compare: F) -> Option<Token> {
+        self.best_by(|a, b| compare(a, b) == Ordering::Greater)
+    }
+
+    /// Find the smallest item and return its token. If several items
+    /// are equally small, the one nearest the front is chosen. If the
+    /// deque is empty, `None` is returned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use token_deque::Deque;
+    ///
+    /// let d: Deque<u8> = vec![3, 7, 2, 7].into_iter().collect();
+    ///
+    /// assert_eq!(Some(&2), d.get(&d.min().unwrap()));
+    /// ```
+    pub fn min(&self) -> Option<Token>
+    where
+        T: Ord,
+    {
+        self.min_by(|a, b| a.cmp(b))
+    }
+
+    /// Find the largest item and return its token. If several items are
+    /// equally large, the one nearest the front is chosen. If the deque
+    /// is empty, `None` is returned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use token_deque::Deque;
+    ///
+    /// let d: Deque<u8> = vec![3, 7, 2, 7].into_iter().collect();
+    ///
+    /// assert_eq!(Some(&7), d.get(&d.max().unwrap()));
+    /// ```
+    pub fn max(&self) -> Option<Token>
+    where
+        T: Ord,
+    {
+        self.max_by(|a, b| a.cmp(b))
+    }
+
+    pub(crate) fn remove_unchecked(&mut self, ix: usize) -> T {
+        let (front, data, back) = self
+            .free(ix)
+            .into_used()
+            .expect("self.slots[ix] must be used in order to remove it")
+            .take();
+
+        if self.front == ix {
+            debug_assert_eq!(usize::MAX, front);
+            self.front = back;
+        } else {
+            debug_assert_ne!(usize::MAX, front);
+            self.slots[front]
+                .get_used_mut()
+                .expect("self.slots[front] should always be a used slot")
+                .set_back(back);
+        }
+
+        if self.back == ix {
+            debug_assert_eq!(usize::MAX, back);
+            self.back = front;
+        } else {
+            debug_assert_ne!(usize::MAX, back);
+            self.slots[back]
+                .get_used_mut()
+                .expect("self.slots[back] should always be a used slot")
+                .set_front(front);
+        }
+
+        data
+    }
+
+    // Get the index of the slot referenced by `token` if the token is
+    // still valid.
+    fn index_of(&self, token: &Token) -> Option<usize> {
+        let Token { ix, generation } = token;
+
+        self.slots
+            .get(*ix)
+            .and_then(|s| s.get_used())
+            .and_then(|u| u.as_generation(*generation))
+            .map(|_| *ix)
+    }
+
+    // True when the used slot at `front` is the same as, or comes
+    // before, the used slot at `back`.
+    fn is_ordered(&self, front: usize, back: usize) -> bool {
+        let mut ix = front;
+
+        while usize::MAX != ix {
+            if back == ix {
+                return true;
+            }
+            ix = self.slots[ix]
+                .get_used()
+                .expect("self.slots[ix] should always be a used slot")
+                .back();
+        }
+
+        false
+    }
+
+    // Get the index of the slot `n` steps from the front. `n` must be
+    // less than `len()`.
+    fn nth_index(&self, n: usize) -> usize {
+        debug_assert!(n < self.len_used);
+
+        let mut ix = self.front;
+        for _ in 0..n {
+            ix = self.slots[ix]
+                .get_used()
+                .expect("self.slots[ix] should always be a used slot")
+                .back();
+        }
+        ix
+    }
+
+    // Get the index of the slot `n` steps from the back. `n` must be
+    // less than `len()`.
+    fn nth_index_back(&self, n: usize) -> usize {
+        debug_assert!(n < self.len_used);
+
+        let mut ix = self.back;
+        for _ in 0..n {
+            ix = self.slots[ix]
+                .get_used()
+                .expect("self.slots[ix] should always be a used slot")
+                .front();
+        }
+        ix
+    }
+
+    // Rotate the deque so that the used slot at `ix` becomes the
+    // front. Everything before it wraps around to the back.
+    fn rotate_to(&mut self, ix: usize) {
+        if self.front == ix {
+            return;
+        }
+
+        let new_back = self.slots[ix]
+            .get_used()
+            .expect("self.slots[ix] should always be a used slot")
+            .front();
+
+        // Join the old back to the old front, closing the ring.
+        self.slots[self.back]
+            .get_used_mut()
+            .expect("self.slots[self.back] should always be a used slot")
+            .set_back(self.front);
+        self.slots[self.front]
+            .get_used_mut()
+            .expect("self.slots[self.front] should always be a used slot")
+            .set_front(self.back);
+
+        // Break the ring just before `ix`.
+        self.slots[new_back]
+            .get_used_mut()
+            .expect("self.slots[new_back] should always be a used slot")
+            .set_back(usize::MAX);
+        self.slots[ix]
+            .get_used_mut()
+            .expect("self.slots[ix] should always be a used slot")
+            .set_front(usize::MAX);
+
+        self.front = ix;
+        self.back = new_back;
+    }
+
+    // Build a token for the used slot at `ix`.
+    fn token_at(&self, ix: usize) -> Token {
+        let generation = self.slots[ix]
+            .get_used()
+            .expect("self.slots[ix] should always be a used slot")
+            .generation();
+
+        Token { ix, generation }
+    }
+
+    // Get the front and back links of the used slot at `ix`.
+    fn links(&self, ix: usize) -> (usize, usize) {
+        let u = self.slots[ix]
+            .get_used()
+            .expect("self.slots[ix] should always be a used slot");
+
+        (u.front(), u.back())
+    }
+
+    // Push `n` new free slots onto the end of `self.slots` and the
+    // free list.
+    fn push_free_slots(&mut self, n: usize) {
+        for _ in 0..n {
+            let ix = self.slots.len();
+            self.slots.push(Slot::new_free(usize::MAX));
+            self.link_free(ix);
+        }
+    }
+
+    // Add the free slot at `ix` to the free list. It goes on the head
+    // of a LIFO free list and on the tail of a FIFO free list.
+    fn link_free(&mut self, ix: usize) {
+        if self.fifo {
+            self.slots[ix] = Slot::new_free(usize::MAX);
+            if usize::MAX == self.free_tail {
+                self.free_list = ix;
+            } else {
+                self.slots[self.free_tail]
+                    .get_free_mut()
+                    .expect("self.slots[self.free_tail] is expected to be free")
+                    .set_next(ix);
+            }
+            self.free_tail = ix;
+        } else {
+            self.slots[ix] = Slot::new_free(self.free_list);
+            if usize::MAX == self.free_list {
+                self.free_tail = ix;
+            }
+            self.free_list = ix;
+        }
+
+        self.len_free += 1;
+    }
+
+    // Find the token of the first item, from front to back, that no
+    // later item beats. `beats(a, b)` is true when `a` should replace
+    // the current best `b`.
+    fn best_by<F: FnMut(&T, &T) -> bool>(&self, mut beats: F) -> Option<Token> {
+        let mut best = self.front;
+        if usize::MAX == best {
+            return None;
+        }
+
+        let mut ix = self.links(best).1;
+        while usize::MAX != ix {
+            let u = self.slots[ix]
+                .get_used()
+                .expect("self.slots[ix] should always be a used slot");
+            let b = self.slots[best]
+                .get_used()
+                .expect("self.slots[best] should always be a used slot");
+            if beats(u.data(), b.data()) {
+                best = ix;
+            }
+            ix = u.back();
+        }
+
+        Some(self.token_at(best))
+    }
+
+    // Detach the used slot at `ix` from its neighbors and join the
+    // neighbors to each other. The slot stays used, but its own links
+    // are stale until it is linked back in with `link_between`.
+    fn unlink(&mut self, ix: usize) {
+        let (front, back) = {
+            let u = self.slots[ix]
+                .get_used()
+                .expect("self.slots[ix] must be used in order to unlink it");
+            (u.front(), u.back())
+        };
+
+        self.set_back_of(front, back);
+        self.set_front_of(back, front);
+    }
+
+    // Link the used slot at `ix` in between `front` and `back`, which
+    // must be adjacent (or MAX to represent the ends of the deque).
+    fn link_between(&mut self, ix: usize, front: usize, back: usize) {
+        {
+            let u = self.slots[ix]
+                .get_used_mut()
+                .expect("self.slots[ix] must be used in order to link it");
+            u.set_front(front);
+            u.set_back(back);
+        }
+
+        self.set_back_of(front, ix);
+        self.set_front_of(back, ix);
+    }
+
+    // Point the back link of the slot at `ix` at `back`. When `ix` is
+    // MAX, `back` becomes the front of the deque instead.
+    fn set_back_of(&mut self, ix: usize, back: usize) {
+        if usize::MAX == ix {
+            self.front = back;
+        } else {
+            self.slots[ix]
+                .get_used_mut()
+                .expect("self.slots[ix] should always be a used slot")
+                .set_back(back);
+        }
+    }
+
+    // Point the front link of the slot at `ix` at `front`. When `ix`
+    // is MAX, `front` becomes the back of the deque instead.
+    fn set_front_of(&mut self, ix: usize, front: usize) {
+        if usize::MAX == ix {
+            self.back = front;
+        } else {
+            self.slots[ix]
+                .get_used_mut()
+                .expect("self.slots[ix] should always be a used slot")
+                .set_front(front);
+        }
+    }
+
+    pub(crate) fn allocate(&mut self, front: usize, back: usize, data: T) -> (usize, usize) {
+        // Assuming a 64 bit usize and that we could add a new item to
+        // the deque 10 billion times per second, it would take ~58
+        // years for the generation to overflow. After that point, the
+        // token that is constructed from the generation could be used
+        // to remove or get an incorrect object from the deque if the
+        // object at that index had the same generation 58 years
+        // prior.
+        //
+        // We do a checked-add in order to save future developers from
+        // having to hunt down this rare problem in ancient code
+        // bases. Instead, we give them a once-in-a-lifetime panic.
+        let generation = self.next_generation;
+        self.next_generation = self
+            .next_generation
+            .checked_add(1)
+            .expect("58 years have passed, or generations have become corrupted");
+
+        self.len_used += 1;
+
+        let s = Slot::new_used(front, back, generation, data);
+
+        let ix = if usize::MAX == self.free_list {
+            self.slots.push(s);
+            self.slots.len() - 1
+        } else {
+            let ix = self.free_list;
+            self.free_list = self.slots[ix]
+                .get_free()
+                .expect("self.slots[self.free_list] is expected to be free")
+                .next();
+            if usize::MAX == self.free_list {
+                self.free_tail = usize::MAX;
+            }
+            self.slots[ix] = s;
+            self.len_free -= 1;
+            ix
+        };
+
+        (ix, generation)
+    }
+
+    pub(crate) fn free(&mut self, ix: usize) -> Slot<T> {
+        debug_assert!(self.slots[ix].get_used().is_some());
+
+        self.len_used -= 1;
+
+        let mut v = Slot::new_free(usize::MAX);
+        std::mem::swap(&mut v, &mut self.slots[ix]);
+        self.link_free(ix);
+        v
+    }
+}
+
+impl<T> FromIterator<T> for Deque<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut l = Self::new();
+        for i in iter {
+            l.push_back(i);
+        }
+        l
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn push_get_works() {
+        let mut l = Deque::new();
+        let t10 = l.push_front(10u8);
+
+        let r = l.get(&t10);
+        assert_eq!(Some(&10), r);
+        let r = l.get_front();
+        assert_eq!(Some(&10), r);
+        let r = l.get_back();
+        assert_eq!(Some(&10), r);
+
+        let mut l = Deque::new();
+        let t11 = l.push_back(11u8);
+
+        let r = l.get(&t11);
+        assert_eq!(Some(&11), r);
+        let r = l.get_front();
+        assert_eq!(Some(&11), r);
+        let r = l.get_back();
+        assert_eq!(Some(&11), r);
+    }
+
+    #[test]
+    fn push_remove_works() {
+        let mut l = Deque::new();
+        let t10 = l.push_front(10u8);
+
+        let r = l.remove(&t10);
+        assert_eq!(Some(10), r);
+    }
+
+    #[test]
+    fn push_remove_front_get_is_none() {
+        let mut l = Deque::new();
+        let t10 = l.push_front(10u8);
+
+        let r = l.get_front();
+        assert_eq!(Some(&10), r);
+
+        let r = l.pop_front();
+        assert_eq!(Some(10), r);
+
+        let r = l.get(&t10);
+        assert_eq!(None, r);
+    }
+
+    #[test]
+    fn push_remove_back_get_is_none() {
+        let mut l = Deque::new();
+        let t10 = l.push_front(10u8);
+
+        let r = l.get_back();
+        assert_eq!(Some(&10), r);
+
+        let r = l.pop_back();
+        assert_eq!(Some(10), r);
+
+        let r = l.get(&t10);
+        assert_eq!(None, r);
+    }
+
+    #[test]
+    fn push_back_works() {
+        let mut l = Deque::new();
+        let t10 = l.push_back(10u8);
+        let t11 = l.push_back(11u8);
+
+        let r = l.get(&t10);
+        assert_eq!(Some(&10), r);
+        let r = l.get(&t11);
+        assert_eq!(Some(&11), r);
+
+        let r = l.get_front();
+        assert_eq!(Some(&10), r);
+        let r = l.get_back();
+        assert_eq!(Some(&11), r);
+    }
+
+    #[test]
+    fn counts_work_as_expected() {
+        let mut l = Deque::new();
+        l.push_front(10u8);
+        l.push_front(11u8);
+        assert_eq!(2, l.len());
+        assert_eq!(0, l.len_freelist());
+
+        l.pop_back();
+        assert_eq!(1, l.len());
+        assert_eq!(1, l.len_freelist());
+
+        l.pop_back();
+        assert_eq!(0, l.len());
+        assert_eq!(2, l.len_freelist());
+
+        l.push_front(12u8);
+        assert_eq!(1, l.len());
+        assert_eq!(1, l.len_freelist());
+
+        l.push_front(13u8);
+        assert_eq!(2, l.len());
+        assert_eq!(0, l.len_freelist());
+    }
+
+    #[test]
+    fn get_mut_allows_values_to_be_replaced() {
+        let mut l = Deque::new();
+        l.push_front(10u8);
+        let t = l.push_front(11u8);
+        l.push_front(12u8);
+
+        if let Some(v) = l.get_mut(&t) {
+            *v = 20;
+        }
+
+        let r = l.pop_back();
+        assert_eq!(Some(10), r);
+        let r = l.pop_back();
+        assert_eq!(Some(20), r);
+        let r = l.pop_back();
+        assert_eq!(Some(12), r);
+    }
+
+    #[test]
+    fn can_be_created_from_iterator() {
+        let mut l = Deque::from_iter(0..5);
+
+        let r = l.pop_front();
+        assert_eq!(Some(0), r);
+        let r = l.pop_front();
+        assert_eq!(Some(1), r);
+        let r = l.pop_front();
+        assert_eq!(Some(2), r);
+        let r = l.pop_front();
+        assert_eq!(Some(3), r);
+        let r = l.pop_front();
+        assert_eq!(Some(4), r);
+    }
+
+    #[test]
+    fn with_capacity_preallocates_free_list() {
+        let mut l = Deque::with_capacity(3);
+        assert_eq!(3, l.len_freelist());
+        assert_eq!(0, l.len());
+
+        l.push_front(());
+        assert_eq!(2, l.len_freelist());
+        assert_eq!(1, l.len());
+
+        // The underlying capacity should not have changed.
+        assert_eq!(3, l.capacity());
+
+        l.push_front(());
+        l.push_front(());
+        l.push_front(());
+
+        assert_eq!(0, l.len_freelist());
+        assert_eq!(4, l.len());
+
+        // The underlying capacity should have expanded to handle 4
+        // items.
+        assert!(3 < l.capacity());
+    }
+
+    #[test]
+    fn get_front_mut_allows_front_to_change_value() {
+        let mut l = Deque::new();
+        l.push_front(10u8);
+
+        if let Some(r) = l.get_front_mut() {
+            *r = 100;
+        }
+
+        assert_eq!(Some(&100), l.get_front());
+    }
+
+    #[test]
+    fn get_back_mut_allows_back_to_change_value() {
+        let mut l = Deque::new();
+        l.push_back(10u8);
+
+        if let Some(r) = l.get_back_mut() {
+            *r = 100;
+        }
+
+        assert_eq!(Some(&100), l.get_front());
+    }
+
+    #[test]
+    fn empty_list() {
+        let mut l: Deque<u8> = Deque::new();
+        let t = l.push_front(1);
+        l.pop_front();
+
+        assert!(l.is_empty());
+
+        assert_eq!(None, l.get_front());
+        assert_eq!(None, l.get_front_mut());
+
+        assert_eq!(None, l.get_back());
+        assert_eq!(None, l.get_back_mut());
+
+        assert_eq!(None, l.pop_front());
+        assert_eq!(None, l.pop_back());
+
+        assert_eq!(None, l.get(&t));
+    }
+
+    #[test]
+    fn generation_protects_against_getting_wrong_item() {
+        let mut l: Deque<u8> = Deque::new();
+
+        // Push in a value, and make sure we can see it.
+        let t0 = l.push_front(1);
+        assert_eq!(Some(&1), l.get(&t0));
+        assert_eq!(Some(&mut 1), l.get_mut(&t0));
+
+        // Pop off the value, and make sure we can no longer see it.
+        l.pop_front();
+        assert_eq!(None, l.get(&t0));
+        assert_eq!(None, l.get_mut(&t0));
+
+        // Push in a new value, and make sure we can see it.
+        let t1 = l.push_front(2);
+        assert_eq!(Some(&2), l.get(&t1));
+        assert_eq!(Some(&mut 2), l.get_mut(&t1));
+
+        // Check the original token, and make sure it's still None.
+        assert_eq!(None, l.get(&t0));
+        assert_eq!(None, l.get_mut(&t0));
+    }
+
+    #[test]
+    fn reserve_increases_capacity() {
+        let mut l: Deque<u8> = Deque::new();
+        l.push_front(1);
+
+        let cap = l.capacity();
+        let res = cap + 16;
+
+        l.reserve(res);
+
+        assert!(l.capacity() >= res);
+    }
+
+    #[test]
+    fn debug_string() {
+        let mut l: Deque<u8> = Deque::new();
+
+        l.push_back(1);
+        l.push_back(2);
+        l.push_back(3);
+
+        assert_eq!("[1, 2, 3]", format!("{:?}", l));
+    }
+
+    #[test]
+    fn default_works() {
+        let mut l: Deque<u8> = Default::default();
+
+        l.push_back(1);
+        l.push_back(2);
+        l.push_back(3);
+
+        assert_eq!(vec![1, 2, 3], l.drain_front().collect::<Vec<u8>>());
+    }
+
+    #[test]
+    fn touch_moves_item_to_front() {
+        let mut l = Deque::new();
+        let t1 = l.push_back(1u8);
+        let t2 = l.push_back(2u8);
+        let t3 = l.push_back(3u8);
+
+        assert!(l.touch(&t2));
+        assert_eq!(vec![&2, &1, &3], l.iter_front().collect::<Vec<&u8>>());
+        assert_eq!(vec![&3, &1, &2], l.iter_back().collect::<Vec<&u8>>());
+
+        // Touching the front again should leave it in place.
+        assert!(l.touch(&t2));
+        assert_eq!(vec![&2, &1, &3], l.iter_front().collect::<Vec<&u8>>());
+        assert_eq!(Some(&2), l.get_front());
+
+        assert!(l.touch(&t3));
+        assert_eq!(vec![&3, &2, &1], l.iter_front().collect::<Vec<&u8>>());
+        assert_eq!(Some(&1), l.get_back());
+
+        assert_eq!(Some(&1), l.get(&t1));
+        assert_eq!(Some(&2), l.get(&t2));
+        assert_eq!(Some(&3), l.get(&t3));
+    }
+
+    #[test]
+    fn touch_with_stale_token_does_nothing() {
+        let mut l = Deque::new();
+        l.push_back(1u8);
+        let t = l.push_back(2u8);
+        l.push_back(3u8);
+        l.remove(&t);
+
+        assert!(!l.touch(&t));
+        assert_eq!(vec![&1, &3], l.iter_front().collect::<Vec<&u8>>());
+    }
+
+    #[test]
+    fn push_front_bounded_evicts_back() {
+        let mut l = Deque::new();
+        let (t1, e) = l.push_front_bounded(1u8, 3);
+        assert_eq!(None, e);
+        let (_, e) = l.push_front_bounded(2u8, 3);
+        assert_eq!(None, e);
+        let (_, e) = l.push_front_bounded(3u8, 3);
+        assert_eq!(None, e);
+        assert_eq!(3, l.len());
+
+        let (t4, e) = l.push_front_bounded(4u8, 3);
+        assert_eq!(Some(1), e);
+        assert_eq!(None, l.get(&t1));
+        assert_eq!(Some(&4), l.get(&t4));
+        assert_eq!(Some(4), l.remove(&t4));
+        assert_eq!(vec![&3, &2], l.iter_front().collect::<Vec<&u8>>());
+
+        // The evicted slot should have been reused rather than grown.
+        assert_eq!(3, l.slots.len());
+    }
+
+    #[test]
+    fn push_back_bounded_evicts_front() {
+        let mut l = Deque::new();
+        let (t1, _) = l.push_back_bounded(1u8, 2);
+        l.push_back_bounded(2u8, 2);
+
+        let (t3, e) = l.push_back_bounded(3u8, 2);
+        assert_eq!(Some(1), e);
+        assert_eq!(None, l.get(&t1));
+        assert_eq!(Some(&3), l.get(&t3));
+        assert_eq!(vec![&2, &3], l.iter_front().collect::<Vec<&u8>>());
+    }
+
+    #[test]
+    fn replace_swaps_in_new_value() {
+        let mut l = Deque::new();
+        l.push_back(1u8);
+        let t = l.push_back(2u8);
+        l.push_back(3u8);
+
+        assert_eq!(Some(2), l.replace(&t, 20));
+        assert_eq!(Some(&20), l.get(&t));
+        assert_eq!(vec![&1, &20, &3], l.iter_front().collect::<Vec<&u8>>());
+    }
+
+    #[test]
+    fn replace_with_stale_token_does_nothing() {
+        let mut l = Deque::new();
+        l.push_back(1u8);
+        let t = l.push_back(2u8);
+        l.remove(&t);
+
+        assert_eq!(None, l.replace(&t, 20));
+        assert_eq!(None, l.get(&t));
+        assert_eq!(vec![&1], l.iter_front().collect::<Vec<&u8>>());
+    }
+
+    #[test]
+    fn update_runs_closure_on_live_item() {
+        let mut l = Deque::new();
+        l.push_back(1u8);
+        let t = l.push_back(2u8);
+
+        assert!(l.update(&t, |v| *v *= 10));
+        assert_eq!(Some(&20), l.get(&t));
+        assert_eq!(vec![&1, &20], l.iter_front().collect::<Vec<&u8>>());
+    }
+
+    #[test]
+    fn update_with_stale_token_does_not_run_closure() {
+        let mut l = Deque::new();
+        let t = l.push_back(1u8);
+        l.remove(&t);
+
+        let mut ran = false;
+        assert!(!l.update(&t, |_| ran = true));
+        assert!(!ran);
+    }
+
+    #[test]
+    fn first_and_last_match_endpoint_getters() {
+        let mut l: Deque<u8> = Deque::new();
+        assert_eq!(None, l.first());
+        assert_eq!(None, l.last());
+        assert_eq!(None, l.first_mut());
+        assert_eq!(None, l.last_mut());
+
+        l.push_back(1);
+        l.push_back(2);
+        l.push_back(3);
+
+        assert_eq!(l.get_front(), l.first());
+        assert_eq!(l.get_back(), l.last());
+        assert_eq!(Some(&mut 1), l.first_mut());
+        assert_eq!(Some(&mut 3), l.last_mut());
+    }
+
+    #[test]
+    fn swap_remove_front_fills_gap_with_front() {
+        let mut l = Deque::new();
+        let t1 = l.push_back(1u8);
+        let t2 = l.push_back(2u8);
+        let t3 = l.push_back(3u8);
+        let t4 = l.push_back(4u8);
+
+        assert_eq!(Some(3), l.swap_remove_front(&t3));
+        assert_eq!(vec![&2, &1, &4], l.iter_front().collect::<Vec<&u8>>());
+        assert_eq!(vec![&4, &1, &2], l.iter_back().collect::<Vec<&u8>>());
+        assert_eq!(Some(&1), l.get(&t1));
+        assert_eq!(Some(&2), l.get(&t2));
+        assert_eq!(Some(&4), l.get(&t4));
+
+        // Removing the neighbor of the front leaves the order alone.
+        assert_eq!(Some(1), l.swap_remove_front(&t1));
+        assert_eq!(vec![&2, &4], l.iter_front().collect::<Vec<&u8>>());
+
+        // Removing the front itself behaves like `pop_front`.
+        assert_eq!(Some(2), l.swap_remove_front(&t2));
+        assert_eq!(vec![&4], l.iter_front().collect::<Vec<&u8>>());
+
+        assert_eq!(None, l.swap_remove_front(&t3));
+    }
+
+    #[test]
+    fn swap_remove_back_fills_gap_with_back() {
+        let mut l = Deque::new();
+        let t1 = l.push_back(1u8);
+        let t2 = l.push_back(2u8);
+        let t3 = l.push_back(3u8);
+        let t4 = l.push_back(4u8);
+
+        assert_eq!(Some(1), l.swap_remove_back(&t1));
+        assert_eq!(vec![&4, &2, &3], l.iter_front().collect::<Vec<&u8>>());
+        assert_eq!(vec![&3, &2, &4], l.iter_back().collect::<Vec<&u8>>());
+        assert_eq!(Some(&2), l.get(&t2));
+        assert_eq!(Some(&3), l.get(&t3));
+        assert_eq!(Some(&4), l.get(&t4));
+        assert_eq!(3, l.len());
+        assert_eq!(1, l.len_freelist());
+    }
+
+    #[test]
+    fn retain_removes_failing_items() {
+        let mut l: Deque<u8> = (1..=6).collect();
+        l.retain(|i| 1 == *i % 2);
+
+        assert_eq!(vec![&1, &3, &5], l.iter_front().collect::<Vec<&u8>>());
+        assert_eq!(vec![&5, &3, &1], l.iter_back().collect::<Vec<&u8>>());
+        assert_eq!(3, l.len_freelist());
+    }
+
+    #[test]
+    fn retain_mut_keeps_mutations_of_survivors() {
+        let mut l = Deque::new();
+        let t1 = l.push_back(1u8);
+        let t2 = l.push_back(2u8);
+        let t3 = l.push_back(3u8);
+        let t4 = l.push_back(4u8);
+
+        l.retain_mut(|i| {
+            *i += 1;
+            0 == *i % 2
+        });
+
+        assert_eq!(vec![&2, &4], l.iter_front().collect::<Vec<&u8>>());
+        assert_eq!(vec![&4, &2], l.iter_back().collect::<Vec<&u8>>());
+        assert_eq!(Some(&2), l.get(&t1));
+        assert_eq!(None, l.get(&t2));
+        assert_eq!(Some(&4), l.get(&t3));
+        assert_eq!(None, l.get(&t4));
+        assert_eq!(2, l.len_freelist());
+    }
+
+    #[test]
+    fn can_be_created_from_iterator_at_front() {
+        let l = Deque::from_iter_front(0..3);
+        assert_eq!(vec![&2, &1, &0], l.iter_front().collect::<Vec<&i32>>());
+
+        let l: Deque<i32> = (0..3).collect();
+        assert_eq!(vec![&0, &1, &2], l.iter_front().collect::<Vec<&i32>>());
+    }
+
+    #[test]
+    fn partition_splits_by_predicate() {
+        let l: Deque<u8> = (1..=4).collect();
+        let (even, odd) = l.partition(|i| 0 == *i % 2);
+
+        assert_eq!(vec![&2, &4], even.iter_front().collect::<Vec<&u8>>());
+        assert_eq!(vec![&4, &2], even.iter_back().collect::<Vec<&u8>>());
+        assert_eq!(vec![&1, &3], odd.iter_front().collect::<Vec<&u8>>());
+        assert_eq!(vec![&3, &1], odd.iter_back().collect::<Vec<&u8>>());
+    }
+
+    #[test]
+    fn rotate_left_moves_front_block_to_back() {
+        let mut l = Deque::new();
+        let t1 = l.push_back(1u8);
+        let t2 = l.push_back(2u8);
+        let t3 = l.push_back(3u8);
+        l.push_back(4u8);
+        let t5 = l.push_back(5u8);
+
+        l.rotate_left(2);
+        assert_eq!(
+            vec![&3, &4, &5, &1, &2],
+            l.iter_front().collect::<Vec<&u8>>()
+        );
+        assert_eq!(
+            vec![&2, &1, &5, &4, &3],
+            l.iter_back().collect::<Vec<&u8>>()
+        );
+        assert_eq!(Some(&1), l.get(&t1));
+        assert_eq!(Some(&2), l.get(&t2));
+        assert_eq!(Some(&3), l.get(&t3));
+        assert_eq!(Some(&5), l.get(&t5));
+
+        l.rotate_left(0);
+        l.rotate_left(5);
+        assert_eq!(
+            vec![&3, &4, &5, &1, &2],
+            l.iter_front().collect::<Vec<&u8>>()
+        );
+    }
+
+    #[test]
+    fn rotate_right_moves_back_block_to_front() {
+        let mut l = Deque::new();
+        let t1 = l.push_back(1u8);
+        l.push_back(2u8);
+        l.push_back(3u8);
+        l.push_back(4u8);
+        let t5 = l.push_back(5u8);
+
+        l.rotate_right(1);
+        assert_eq!(
+            vec![&5, &1, &2, &3, &4],
+            l.iter_front().collect::<Vec<&u8>>()
+        );
+        assert_eq!(
+            vec![&4, &3, &2, &1, &5],
+            l.iter_back().collect::<Vec<&u8>>()
+        );
+        assert_eq!(Some(&1), l.get(&t1));
+        assert_eq!(Some(&5), l.get(&t5));
+
+        l.rotate_right(7);
+        assert_eq!(
+            vec![&3, &4, &5, &1, &2],
+            l.iter_front().collect::<Vec<&u8>>()
+        );
+
+        let mut e: Deque<u8> = Deque::new();
+        e.rotate_left(3);
+        e.rotate_right(3);
+        assert!(e.is_empty());
+    }
+
+    #[test]
+    fn clone_copies_items_and_tokens() {
+        let mut l = Deque::new();
+        let t1 = l.push_back(1u8);
+        let t2 = l.push_back(2u8);
+        l.remove(&t1);
+
+        let c = l.clone();
+        assert_eq!(vec![&2], c.iter_front().collect::<Vec<&u8>>());
+        assert_eq!(None, c.get(&t1));
+        assert_eq!(Some(&2), c.get(&t2));
+        assert_eq!(1, c.len_freelist());
+    }
+
+    #[test]
+    fn clone_from_reuses_allocation() {
+        let mut source = Deque::new();
+        let t1 = source.push_back(1u8);
+        let t2 = source.push_back(2u8);
+        let t3 = source.push_back(3u8);
+        source.remove(&t2);
+
+        let mut target: Deque<u8> = (0..64).collect();
+        let cap = target.capacity();
+
+        target.clone_from(&source);
+        assert_eq!(cap, target.capacity());
+        assert_eq!(vec![&1, &3], target.iter_front().collect::<Vec<&u8>>());
+        assert_eq!(vec![&3, &1], target.iter_back().collect::<Vec<&u8>>());
+        assert_eq!(Some(&1), target.get(&t1));
+        assert_eq!(None, target.get(&t2));
+        assert_eq!(Some(&3), target.get(&t3));
+        assert_eq!(source.len(), target.len());
+        assert_eq!(source.len_freelist(), target.len_freelist());
+
+        // Both should hand out the same token for the next push.
+        assert_eq!(source.push_back(4), target.push_back(4));
+    }
+
+    #[test]
+    fn try_reserve_reports_failures() {
+        let mut l: Deque<u8> = Deque::new();
+        assert!(l.try_reserve(0).is_ok());
+        assert!(l.try_reserve(16).is_ok());
+        assert!(l.capacity() >= 16);
+        assert_eq!(0, l.len_freelist());
+
+        assert!(l.try_reserve(usize::MAX).is_err());
+    }
+
+    #[test]
+    fn try_reserve_freelist_adds_free_slots() {
+        let mut l: Deque<u8> = Deque::new();
+        let t = l.push_back(1);
+
+        assert!(l.try_reserve_freelist(3).is_ok());
+        assert_eq!(3, l.len_freelist());
+        assert_eq!(4, l.slots.len());
+
+        assert!(l.try_reserve_freelist(usize::MAX).is_err());
+        assert_eq!(3, l.len_freelist());
+        assert_eq!(4, l.slots.len());
+
+        l.push_back(2);
+        l.push_back(3);
+        l.push_back(4);
+        assert_eq!(0, l.len_freelist());
+        assert_eq!(4, l.slots.len());
+        assert_eq!(Some(&1), l.get(&t));
+        assert_eq!(vec![&1, &2, &3, &4], l.iter_front().collect::<Vec<&u8>>());
+    }
+
+    #[test]
+    fn validate_detects_corruption() {
+        let mut l: Deque<u8> = (1..=4).collect();
+        l.pop_back();
+        assert_eq!(Ok(()), l.validate());
+
+        let mut c = l.clone();
+        c.len_used += 1;
+        assert!(c.validate().is_err());
+
+        let mut c = l.clone();
+        c.back = c.front;
+        assert!(c.validate().is_err());
+
+        let mut c = l.clone();
+        c.len_free = 0;
+        assert!(c.validate().is_err());
+
+        let mut c = l.clone();
+        c.free_list = c.front;
+        assert!(c.validate().is_err());
+    }
+
+    #[test]
+    fn drains_leave_endpoints_consistent() {
+        let mut l: Deque<u8> = (1..=4).collect();
+        assert_eq!(Some(1), l.drain_front().next());
+        assert_eq!(Ok(()), l.validate());
+        assert_eq!(vec![&2, &3, &4], l.iter_front().collect::<Vec<&u8>>());
+
+        assert_eq!(Some(4), l.drain_back().next());
+        assert_eq!(Ok(()), l.validate());
+        assert_eq!(vec![&2, &3], l.iter_front().collect::<Vec<&u8>>());
+
+        l.drain_front().for_each(drop);
+        assert_eq!(Ok(()), l.validate());
+        l.push_front(5);
+        assert_eq!(vec![&5], l.iter_front().collect::<Vec<&u8>>());
+    }
+
+    #[test]
+    fn split_at_divides_by_position() {
+        let mut l: Deque<u8> = (1..=5).collect();
+        let r = l.split_at(0);
+        assert!(l.is_empty());
+        assert_eq!(
+            vec![&1, &2, &3, &4, &5],
+            r.iter_front().collect::<Vec<&u8>>()
+        );
+        assert_eq!(Ok(()), l.validate());
+        assert_eq!(Ok(()), r.validate());
+
+        let mut l = Deque::new();
+        let t1 = l.push_back(1u8);
+        let t2 = l.push_back(2u8);
+        let t3 = l.push_back(3u8);
+        l.push_back(4u8);
+        l.push_back(5u8);
+        let r = l.split_at(2);
+        assert_eq!(vec![&1, &2], l.iter_front().collect::<Vec<&u8>>());
+        assert_eq!(vec![&2, &1], l.iter_back().collect::<Vec<&u8>>());
+        assert_eq!(vec![&3, &4, &5], r.iter_front().collect::<Vec<&u8>>());
+        assert_eq!(vec![&5, &4, &3], r.iter_back().collect::<Vec<&u8>>());
+        assert_eq!(Some(&1), l.get(&t1));
+        assert_eq!(Some(&2), l.get(&t2));
+        assert_eq!(None, l.get(&t3));
+        assert_eq!(3, l.len_freelist());
+
+        let mut l: Deque<u8> = (1..=5).collect();
+        let r = l.split_at(5);
+        assert_eq!(
+            vec![&1, &2, &3, &4, &5],
+            l.iter_front().collect::<Vec<&u8>>()
+        );
+        assert!(r.is_empty());
+    }
+
+    #[test]
+    fn insert_at_places_items_by_position() {
+        let mut l: Deque<u8> = (1..=3).collect();
+
+        let t0 = l.insert_at(0, 0);
+        assert_eq!(vec![&0, &1, &2, &3], l.iter_front().collect::<Vec<&u8>>());
+
+        let t10 = l.insert_at(2, 10);
+        assert_eq!(
+            vec![&0, &1, &10, &2, &3],
+            l.iter_front().collect::<Vec<&u8>>()
+        );
+        assert_eq!(
+            vec![&3, &2, &10, &1, &0],
+            l.iter_back().collect::<Vec<&u8>>()
+        );
+
+        let t20 = l.insert_at(100, 20);
+        assert_eq!(
+            vec![&0, &1, &10, &2, &3, &20],
+            l.iter_front().collect::<Vec<&u8>>()
+        );
+
+        assert_eq!(Some(&0), l.get(&t0));
+        assert_eq!(Some(&10), l.get(&t10));
+        assert_eq!(Some(&20), l.get(&t20));
+        assert_eq!(Ok(()), l.validate());
+    }
+
+    #[test]
+    fn remove_at_removes_by_position() {
+        let mut l: Deque<u8> = (1..=4).collect();
+
+        assert_eq!(None, l.remove_at(4));
+        assert_eq!(Some(3), l.remove_at(2));
+        assert_eq!(Some(1), l.remove_at(0));
+        assert_eq!(Some(4), l.remove_at(1));
+        assert_eq!(vec![&2], l.iter_front().collect::<Vec<&u8>>());
+        assert_eq!(Ok(()), l.validate());
+    }
+
+    #[test]
+    fn count_where_any_and_all() {
+        let l: Deque<u8> = vec![1, 2, 3, 4, 6].into_iter().collect();
+        assert_eq!(3, l.count_where(|i| 0 == *i % 2));
+        assert_eq!(0, l.count_where(|i| *i > 6));
+        assert!(l.any(|i| 0 == *i % 2));
+        assert!(!l.any(|i| *i > 6));
+        assert!(l.all(|i| *i > 0));
+        assert!(!l.all(|i| 0 == *i % 2));
+
+        let mut visited = 0;
+        assert!(l.any(|i| {
+            visited += 1;
+            2 == *i
+        }));
+        assert_eq!(2, visited);
+
+        let e: Deque<u8> = Deque::new();
+        assert_eq!(0, e.count_where(|_| true));
+        assert!(!e.any(|_| true));
+        assert!(e.all(|_| false));
+    }
+
+    #[test]
+    fn find_token_finds_first_match() {
+        let mut l = Deque::new();
+        l.push_back(1u8);
+        let t2 = l.push_back(2u8);
+        l.push_back(3u8);
+        l.push_back(2u8);
+
+        let t = l.find_token(|i| 2 == *i).unwrap();
+        assert_eq!(t2, t);
+        assert_eq!(Some(2), l.remove(&t));
+        assert_eq!(vec![&1, &3, &2], l.iter_front().collect::<Vec<&u8>>());
+
+        assert_eq!(None, l.find_token(|i| 4 == *i));
+    }
+
+    #[test]
+    fn retain_collect_returns_removed_items() {
+        let mut l: Deque<u8> = (1..=4).collect();
+
+        assert_eq!(vec![2, 4], l.retain_collect(|i| 1 == *i % 2));
+        assert_eq!(vec![&1, &3], l.iter_front().collect::<Vec<&u8>>());
+        assert_eq!(2, l.len_freelist());
+        assert_eq!(Ok(()), l.validate());
+
+        assert!(l.retain_collect(|_| true).is_empty());
+    }
+
+    #[test]
+    fn swap_nodes_exchanges_positions() {
+        let mut l = Deque::new();
+        let t1 = l.push_back(1u8);
+        let t2 = l.push_back(2u8);
+        let t3 = l.push_back(3u8);
+        let t4 = l.push_back(4u8);
+
+        // Adjacent, in both argument orders.
+        assert!(l.swap_nodes(&t1, &t2));
+        assert_eq!(vec![&2, &1, &3, &4], l.iter_front().collect::<Vec<&u8>>());
+        assert!(l.swap_nodes(&t1, &t2));
+        assert_eq!(vec![&1, &2, &3, &4], l.iter_front().collect::<Vec<&u8>>());
+        assert!(l.swap_nodes(&t4, &t3));
+        assert_eq!(vec![&1, &2, &4, &3], l.iter_front().collect::<Vec<&u8>>());
+        assert_eq!(Ok(()), l.validate());
+
+        // Non-adjacent, including both ends.
+        assert!(l.swap_nodes(&t1, &t3));
+        assert_eq!(vec![&3, &2, &4, &1], l.iter_front().collect::<Vec<&u8>>());
+        assert!(l.swap_nodes(&t2, &t1));
+        assert_eq!(vec![&3, &1, &4, &2], l.iter_front().collect::<Vec<&u8>>());
+        assert_eq!(vec![&2, &4, &1, &3], l.iter_back().collect::<Vec<&u8>>());
+        assert_eq!(Ok(()), l.validate());
+
+        assert_eq!(Some(&1), l.get(&t1));
+        assert_eq!(Some(&2), l.get(&t2));
+        assert_eq!(Some(&3), l.get(&t3));
+        assert_eq!(Some(&4), l.get(&t4));
+
+        assert!(l.swap_nodes(&t4, &t4));
+        l.remove(&t4);
+        assert!(!l.swap_nodes(&t1, &t4));
+        assert_eq!(vec![&3, &1, &2], l.iter_front().collect::<Vec<&u8>>());
+    }
+
+    #[test]
+    fn collect_tokens_returns_live_tokens_in_order() {
+        let l0: Deque<u8> = Deque::new();
+        assert!(l0.collect_tokens().is_empty());
+
+        let mut l: Deque<u8> = vec![10, 20, 30].into_iter().collect();
+        let tokens = l.collect_tokens();
+        assert_eq!(3, tokens.len());
+
+        assert_eq!(Some(20), l.remove(&tokens[1]));
+        assert_eq!(Some(&10), l.get(&tokens[0]));
+        assert_eq!(None, l.get(&tokens[1]));
+        assert_eq!(Some(&30), l.get(&tokens[2]));
+        assert_eq!(vec![tokens[0], tokens[2]], l.collect_tokens());
+    }
+
+    #[test]
+    fn with_capacity_exact_allocates_exactly() {
+        for &n in &[0, 1, 7, 13, 100] {
+            let mut l: Deque<u8> = Deque::with_capacity_exact(n);
+            assert_eq!(n, l.capacity());
+            assert_eq!(n, l.len_freelist());
+            assert_eq!(Ok(()), l.validate());
+
+            for i in 0..n {
+                l.push_back(i as u8);
+            }
+            assert_eq!(n, l.capacity());
+            assert_eq!(0, l.len_freelist());
+        }
+    }
+
+    #[test]
+    fn shrink_to_respects_floor_and_tokens() {
+        let mut l: Deque<u32> = Deque::new();
+        let tokens: Vec<Token> = (0..64).map(|i| l.push_back(i)).collect();
+
+        // Keep a few items scattered through the slots, including one
+        // near the end.
+        for (i, t) in tokens.iter().enumerate() {
+            if 0 != i % 20 {
+                l.remove(t);
+            }
+        }
+        assert_eq!(4, l.len());
+        assert_eq!(60, l.len_freelist());
+
+        l.shrink_to(8);
+        assert_eq!(61, l.slots.len());
+        assert_eq!(57, l.len_freelist());
+        assert_eq!(Ok(()), l.validate());
+
+        l.remove(&tokens[60]);
+        l.shrink_to(16);
+        assert_eq!(41, l.slots.len());
+        assert!(l.capacity() >= 41);
+        assert_eq!(Ok(()), l.validate());
+
+        l.remove(&tokens[40]);
+        l.remove(&tokens[20]);
+        l.shrink_to(8);
+        assert_eq!(8, l.slots.len());
+        assert_eq!(8, l.capacity());
+        assert_eq!(7, l.len_freelist());
+        assert_eq!(Ok(()), l.validate());
+
+        assert_eq!(Some(&0), l.get(&tokens[0]));
+        assert_eq!(None, l.get(&tokens[40]));
+        assert_eq!(vec![&0], l.iter_front().collect::<Vec<&u32>>());
+
+        // Slots that were released can be allocated again without
+        // reviving old tokens.
+        for i in 0..60 {
+            l.push_back(i);
+        }
+        assert_eq!(None, l.get(&tokens[40]));
+        assert_eq!(Ok(()), l.validate());
+    }
+
+    #[test]
+    fn tokens_can_be_copied() {
+        fn lookup(l: &Deque<u8>, t: Token) -> Option<&u8> {
+            l.get(&t)
+        }
+
+        let mut l = Deque::new();
+        let t = l.push_back(1u8);
+
+        assert_eq!(Some(&1), lookup(&l, t));
+        assert_eq!(Some(&1), lookup(&l, t));
+
+        let copy = t;
+        assert_eq!(Some(1), l.remove(&copy));
+        assert_eq!(None, l.get(&t));
+    }
+
+    #[test]
+    fn tokens_can_be_ordered() {
+        use std::collections::BTreeSet;
+
+        let mut l = Deque::new();
+        let t0 = l.push_front(0u8);
+        let t1 = l.push_front(1u8);
+        l.remove(&t0);
+        let t2 = l.push_front(2u8);
+
+        let set: BTreeSet<Token> = vec![t2, t1, t0, t1, t2].into_iter().collect();
+        assert_eq!(3, set.len());
+
+        // Ordered by location first, then by generation. `t2` reused
+        // the slot that `t0` was in.
+        assert_eq!(vec![&t0, &t2, &t1], set.iter().collect::<Vec<&Token>>());
+    }
+
+    #[test]
+    fn as_ref_vec_matches_iter_front() {
+        let mut l: Deque<u8> = (1..=5).collect();
+        l.remove_at(1);
+
+        let v = l.as_ref_vec();
+        assert_eq!(l.iter_front().collect::<Vec<&u8>>(), v);
+        assert_eq!(v.len(), v.capacity());
+
+        let e: Deque<u8> = Deque::new();
+        assert!(e.as_ref_vec().is_empty());
+    }
+
+    #[test]
+    fn extend_back_returns_tokens_in_insertion_order() {
+        let mut l: Deque<u8> = Deque::new();
+        l.push_back(0);
+
+        let tokens = l.extend_back(vec![1, 2, 3]);
+        assert_eq!(3, tokens.len());
+        assert_eq!(Some(2), l.remove(&tokens[1]));
+        assert_eq!(vec![&0, &1, &3], l.iter_front().collect::<Vec<&u8>>());
+        assert_eq!(Some(&1), l.get(&tokens[0]));
+        assert_eq!(Some(&3), l.get(&tokens[2]));
+    }
+
+    #[test]
+    fn extend_front_returns_tokens_in_insertion_order() {
+        let mut l: Deque<u8> = Deque::new();
+        l.push_back(0);
+
+        let tokens = l.extend_front(1..=3);
+        assert_eq!(vec![&3, &2, &1, &0], l.iter_front().collect::<Vec<&u8>>());
+        assert_eq!(Some(&1), l.get(&tokens[0]));
+        assert_eq!(Some(&2), l.get(&tokens[1]));
+        assert_eq!(Some(&3), l.get(&tokens[2]));
+        assert_eq!(Ok(()), l.validate());
+    }
+
+    #[test]
+    fn merge_sorted_interleaves_sorted_inputs() {
+        let a: Deque<u8> = vec![1, 3, 5].into_iter().collect();
+        let b: Deque<u8> = vec![2, 4, 6].into_iter().collect();
+        let m = a.merge_sorted(b);
+        assert_eq!(
+            vec![&1, &2, &3, &4, &5, &6],
+            m.iter_front().collect::<Vec<&u8>>()
+        );
+        assert_eq!(Ok(()), m.validate());
+        assert_eq!(0, m.len_freelist());
+
+        let a: Deque<u8> = vec![1, 2].into_iter().collect();
+        let b: Deque<u8> = Deque::new();
+        let m = b.merge_sorted(a);
+        assert_eq!(vec![&1, &2], m.iter_front().collect::<Vec<&u8>>());
+    }
+
+    #[test]
+    fn merge_sorted_by_is_stable() {
+        let a: Deque<(u8, char)> = vec![(1, 'a'), (2, 'a')].into_iter().collect();
+        let b: Deque<(u8, char)> = vec![(1, 'b'), (3, 'b')].into_iter().collect();
+        let m = a.merge_sorted_by(b, |x, y| x.0.cmp(&y.0));
+        assert_eq!(
+            vec![&(1, 'a'), &(1, 'b'), &(2, 'a'), &(3, 'b')],
+            m.iter_front().collect::<Vec<&(u8, char)>>()
+        );
+    }
+
+    #[test]
+    fn interleave_alternates_and_appends_remainder() {
+        let a: Deque<u8> = vec![1, 3, 5].into_iter().collect();
+        let b: Deque<u8> = vec![2, 4].into_iter().collect();
+        let i = a.interleave(b);
+        assert_eq!(
+            vec![&1, &2, &3, &4, &5],
+            i.iter_front().collect::<Vec<&u8>>()
+        );
+        assert_eq!(Ok(()), i.validate());
+
+        let a: Deque<u8> = vec![1].into_iter().collect();
+        let b: Deque<u8> = vec![2, 4, 6].into_iter().collect();
+        let i = a.interleave(b);
+        assert_eq!(vec![&1, &2, &4, &6], i.iter_front().collect::<Vec<&u8>>());
+    }
+
+    #[test]
+    fn dedup_by_key_removes_consecutive_duplicates() {
+        #[derive(Debug, PartialEq)]
+        struct Record {
+            id: u8,
+            name: &'static str,
+        }
+
+        let mut l = Deque::new();
+        let t1 = l.push_back(Record { id: 1, name: "a" });
+        l.push_back(Record { id: 1, name: "b" });
+        let t2 = l.push_back(Record { id: 2, name: "c" });
+        let t3 = l.push_back(Record { id: 1, name: "d" });
+
+        let free = l.len_freelist();
+        l.dedup_by_key(|r| r.id);
+
+        assert_eq!(free + 1, l.len_freelist());
+        assert_eq!(
+            vec!["a", "c", "d"],
+            l.iter_front().map(|r| r.name).collect::<Vec<&str>>()
+        );
+        assert_eq!(Some("a"), l.get(&t1).map(|r| r.name));
+        assert_eq!(Some("c"), l.get(&t2).map(|r| r.name));
+        assert_eq!(Some("d"), l.get(&t3).map(|r| r.name));
+        assert_eq!(Ok(()), l.validate());
+    }
+
+    #[test]
+    fn get_or_insert_returns_existing_item() {
+        let mut l = Deque::new();
+        l.push_back(1u8);
+        let t = l.push_back(2u8);
+
+        let (t2, v) = l.get_or_insert_front(&t, || panic!("should not be called"));
+        assert_eq!(t, t2);
+        *v = 20;
+        let (t3, v) = l.get_or_insert_back(&t, || panic!("should not be called"));
+        assert_eq!(t, t3);
+        assert_eq!(20, *v);
+        assert_eq!(vec![&1, &20], l.iter_front().collect::<Vec<&u8>>());
+    }
+
+    #[test]
+    fn get_or_insert_inserts_on_stale_token() {
+        let mut l = Deque::new();
+        l.push_back(1u8);
+        let t = l.push_back(2u8);
+        l.remove(&t);
+
+        let (tf, v) = l.get_or_insert_front(&t, || 3);
+        assert_ne!(t, tf);
+        assert_eq!(3, *v);
+        assert_eq!(Some(&3), l.get(&tf));
+
+        l.remove(&tf);
+        let (tb, v) = l.get_or_insert_back(&tf, || 4);
+        assert_ne!(tf, tb);
+        assert_eq!(4, *v);
+        assert_eq!(vec![&1, &4], l.iter_front().collect::<Vec<&u8>>());
+    }
+
+    #[test]
+    fn nth_token_survives_position_changes() {
+        let mut l: Deque<u8> = (1..=4).collect();
+
+        let t = l.nth_token(2).unwrap();
+        assert_eq!(Some(&3), l.get(&t));
+
+        l.remove_at(0);
+        assert_eq!(Some(&3), l.get(&t));
+        assert_eq!(Some(t), l.nth_token(1));
+        assert_eq!(None, l.nth_token(3));
+    }
+
+    #[test]
+    fn remove_if_only_removes_matching_items() {
+        let mut l = Deque::new();
+        l.push_back(1u8);
+        let t = l.push_back(2u8);
+
+        assert_eq!(None, l.remove_if(&t, |i| *i > 2));
+        assert_eq!(Some(&2), l.get(&t));
+        assert_eq!(2, l.len());
+
+        assert_eq!(Some(2), l.remove_if(&t, |i| *i == 2));
+        assert_eq!(None, l.get(&t));
+        assert_eq!(vec![&1], l.iter_front().collect::<Vec<&u8>>());
+
+        assert_eq!(None, l.remove_if(&t, |_| panic!("should not be called")));
+    }
+
+    #[test]
+    fn reverse_range_reverses_interior() {
+        let mut l = Deque::new();
+        let t1 = l.push_back(1u8);
+        let t2 = l.push_back(2u8);
+        let t3 = l.push_back(3u8);
+        let t4 = l.push_back(4u8);
+        let t5 = l.push_back(5u8);
+
+        assert!(l.reverse_range(&t2, &t4));
+        assert_eq!(
+            vec![&1, &4, &3, &2, &5],
+            l.iter_front().collect::<Vec<&u8>>()
+        );
+        assert_eq!(
+            vec![&5, &2, &3, &4, &1],
+            l.iter_back().collect::<Vec<&u8>>()
+        );
+        assert_eq!(Ok(()), l.validate());
+        assert_eq!(Some(&1), l.get(&t1));
+        assert_eq!(Some(&2), l.get(&t2));
+        assert_eq!(Some(&3), l.get(&t3));
+        assert_eq!(Some(&4), l.get(&t4));
+        assert_eq!(Some(&5), l.get(&t5));
+
+        // The old range is now out of order.
+        assert!(!l.reverse_range(&t2, &t4));
+
+        assert!(l.reverse_range(&t1, &t5));
+        assert_eq!(
+            vec![&5, &2, &3, &4, &1],
+            l.iter_front().collect::<Vec<&u8>>()
+        );
+        assert_eq!(Ok(()), l.validate());
+
+        assert!(l.reverse_range(&t3, &t3));
+        assert_eq!(
+            vec![&5, &2, &3, &4, &1],
+            l.iter_front().collect::<Vec<&u8>>()
+        );
+
+        l.remove(&t3);
+        assert!(!l.reverse_range(&t3, &t1));
+    }
+
+    #[test]
+    fn prepend_moves_items_onto_front() {
+        let mut a = Deque::new();
+        let t3 = a.push_back(3u8);
+        a.push_back(4u8);
+        let mut b: Deque<u8> = vec![1, 2].into_iter().collect();
+
+        a.prepend(&mut b);
+        assert_eq!(vec![&1, &2, &3, &4], a.iter_front().collect::<Vec<&u8>>());
+        assert_eq!(vec![&4, &3, &2, &1], a.iter_back().collect::<Vec<&u8>>());
+        assert_eq!(Some(&3), a.get(&t3));
+        assert!(b.is_empty());
+        assert_eq!(2, b.len_freelist());
+        assert_eq!(Ok(()), a.validate());
+        assert_eq!(Ok(()), b.validate());
+
+        let mut e = Deque::new();
+        let mut c: Deque<u8> = vec![1, 2].into_iter().collect();
+        e.prepend(&mut c);
+        assert_eq!(vec![&1, &2], e.iter_front().collect::<Vec<&u8>>());
+        assert_eq!(Some(&2), e.get_back());
+    }
+
+    #[test]
+    fn keep_last_n_drops_from_front() {
+        let mut l = Deque::new();
+        let t1 = l.push_back(1u8);
+        l.push_back(2u8);
+        l.push_back(3u8);
+        let t4 = l.push_back(4u8);
+        l.push_back(5u8);
+
+        l.keep_last_n(2);
+        assert_eq!(vec![&4, &5], l.iter_front().collect::<Vec<&u8>>());
+        assert_eq!(3, l.len_freelist());
+        assert_eq!(None, l.get(&t1));
+        assert_eq!(Some(&4), l.get(&t4));
+
+        l.keep_last_n(5);
+        assert_eq!(2, l.len());
+        assert_eq!(Ok(()), l.validate());
+    }
+
+    #[test]
+    fn keep_first_n_drops_from_back() {
+        let mut l: Deque<u8> = (1..=5).collect();
+
+        l.keep_first_n(3);
+        assert_eq!(vec![&1, &2, &3], l.iter_front().collect::<Vec<&u8>>());
+        assert_eq!(2, l.len_freelist());
+
+        l.keep_first_n(0);
+        assert!(l.is_empty());
+        assert_eq!(5, l.len_freelist());
+        assert_eq!(Ok(()), l.validate());
+    }
+
+    #[test]
+    fn generations_advance_with_allocations() {
+        let mut l: Deque<u8> = Deque::with_capacity(4);
+        let start = l.next_generation();
+        let remaining = l.remaining_generations();
+
+        for i in 0..3 {
+            l.push_back(i);
+            l.pop_front();
+        }
+        l.push_front(4);
+
+        assert_eq!(start + 4, l.next_generation());
+        assert_eq!(remaining - 4, l.remaining_generations());
+
+        // Removing items does not consume generations.
+        l.pop_back();
+        assert_eq!(start + 4, l.next_generation());
+    }
+
+    #[test]
+    fn clone_compact_packs_items_and_remaps_tokens() {
+        let mut l: Deque<u8> = Deque::new();
+        let tokens = l.extend_back(0..10);
+        for t in tokens.iter().step_by(2) {
+            l.remove(t);
+        }
+        l.touch(&tokens[9]);
+        assert_eq!(5, l.len_freelist());
+
+        let (c, remap) = l.clone_compact();
+        assert_eq!(0, c.len_freelist());
+        assert_eq!(5, c.len());
+        assert_eq!(5, c.slots.len());
+        assert_eq!(Ok(()), c.validate());
+        assert_eq!(
+            l.iter_front().collect::<Vec<&u8>>(),
+            c.iter_front().collect::<Vec<&u8>>()
+        );
+
+        // The front of the clone is packed into the first slot.
+        assert_eq!(0, c.front);
+
+        assert_eq!(5, remap.len());
+        for t in tokens.iter().skip(1).step_by(2) {
+            assert_eq!(l.get(t), c.get(&remap[t]));
+        }
+
+        // The source is untouched.
+        assert_eq!(5, l.len_freelist());
+        assert_eq!(Ok(()), l.validate());
+    }
+
+    #[test]
+    fn debug_layout_reports_chain_in_order() {
+        let mut l: Deque<u8> = (0..5).collect();
+        let t = l.nth_token(3).unwrap();
+        l.touch(&t);
+        l.remove_at(2);
+
+        let layout = l.debug_layout();
+        assert_eq!(l.front, layout.front);
+        assert_eq!(l.back, layout.back);
+        assert_eq!(l.len(), layout.len_used);
+        assert_eq!(l.len_freelist(), layout.len_free);
+        assert_eq!(vec![3, 0, 2, 4], layout.chain);
+        assert_eq!(1, layout.free_list);
+        assert_eq!(
+            l.iter_front().collect::<Vec<&u8>>(),
+            layout
+                .chain
+                .iter()
+                .map(|ix| l.slots[*ix].get_used().unwrap().data())
+                .collect::<Vec<&u8>>()
+        );
+
+        let e: Deque<u8> = Deque::new();
+        let layout = e.debug_layout();
+        assert_eq!(usize::MAX, layout.front);
+        assert_eq!(usize::MAX, layout.back);
+        assert!(layout.chain.is_empty());
+    }
+
+    #[test]
+    fn retain_tokens_sees_each_token() {
+        use std::collections::HashSet;
+
+        let mut l: Deque<u8> = Deque::new();
+        let tokens = l.extend_back(1..=5);
+        let allowed: HashSet<Token> = vec![tokens[0], tokens[3], tokens[4]].into_iter().collect();
+
+        let mut seen = Vec::new();
+        l.retain_tokens(|t, v| {
+            seen.push((*t, *v));
+            allowed.contains(t)
+        });
+
+        assert_eq!(
+            tokens
+                .iter()
+                .copied()
+                .zip(1..=5)
+                .collect::<Vec<(Token, u8)>>(),
+            seen
+        );
+        assert_eq!(vec![&1, &4, &5], l.iter_front().collect::<Vec<&u8>>());
+        assert_eq!(Some(&4), l.get(&tokens[3]));
+        assert_eq!(None, l.get(&tokens[1]));
+        assert_eq!(Ok(()), l.validate());
+    }
+
+    #[test]
+    fn get_front_back_mut_needs_two_items() {
+        let mut l: Deque<u8> = Deque::new();
+        assert_eq!(None, l.get_front_back_mut());
+
+        l.push_back(1);
+        assert_eq!(None, l.get_front_back_mut());
+
+        l.push_back(2);
+        if let Some((f, b)) = l.get_front_back_mut() {
+            std::mem::swap(f, b);
+        }
+        assert_eq!(vec![&2, &1], l.iter_front().collect::<Vec<&u8>>());
+
+        // The front may live after the back in the underlying `Vec`.
+        l.push_back(4);
+        l.push_front(3);
+        assert!(l.front > l.back);
+        if let Some((f, b)) = l.get_front_back_mut() {
+            *f *= 10;
+            *b *= 10;
+        }
+        assert_eq!(vec![&30, &2, &1, &40], l.iter_front().collect::<Vec<&u8>>());
+    }
+
+    #[test]
+    fn swap_front_back_swaps_values_in_place() {
+        let mut l = Deque::new();
+        let t1 = l.push_back(1u8);
+        let t2 = l.push_back(2u8);
+        let t3 = l.push_back(3u8);
+
+        l.swap_front_back();
+        assert_eq!(vec![&3, &2, &1], l.iter_front().collect::<Vec<&u8>>());
+        assert_eq!(Some(&3), l.get(&t1));
+        assert_eq!(Some(&2), l.get(&t2));
+        assert_eq!(Some(&1), l.get(&t3));
+        assert_eq!(Some(t1), l.nth_token(0));
+
+        let mut s: Deque<u8> = Deque::new();
+        s.swap_front_back();
+        s.push_back(1);
+        s.swap_front_back();
+        assert_eq!(vec![&1], s.iter_front().collect::<Vec<&u8>>());
+    }
+
+    #[test]
+    fn drain_all_into_appends_to_sink() {
+        let mut l: Deque<u8> = (1..=3).collect();
+        let mut v = vec![7, 8];
+
+        l.drain_all_into(&mut v);
+        assert_eq!(vec![7, 8, 1, 2, 3], v);
+        assert!(l.is_empty());
+        assert_eq!(3, l.len_freelist());
+        assert_eq!(None, l.get_front());
+        assert_eq!(None, l.get_back());
+        assert_eq!(Ok(()), l.validate());
+
+        l.drain_all_into(&mut v);
+        assert_eq!(5, v.len());
+    }
+
+    #[test]
+    fn is_front_and_is_back_check_endpoints() {
+        let mut l = Deque::new();
+        let a = l.push_back(1);
+        let b = l.push_back(2);
+        let c = l.push_back(3);
+
+        assert!(l.is_front(&a));
+        assert!(!l.is_back(&a));
+        assert!(!l.is_front(&b));
+        assert!(!l.is_back(&b));
+        assert!(l.is_back(&c));
+        assert!(!l.is_front(&c));
+
+        l.pop_front();
+        assert!(!l.is_front(&a));
+        assert!(!l.is_back(&a));
+        assert!(l.is_front(&b));
+    }
+
+    #[test]
+    fn into_sorted_vec_sorts_items() {
+        let l: Deque<u8> = vec![3, 1, 2].into_iter().collect();
+        assert_eq!(vec![1, 2, 3], l.into_sorted_vec());
+
+        let l: Deque<(u8, char)> = vec![(2, 'a'), (1, 'b'), (2, 'c')].into_iter().collect();
+        assert_eq!(
+            vec![(1, 'b'), (2, 'a'), (2, 'c')],
+            l.into_sorted_vec_by(|a, b| a.0.cmp(&b.0))
+        );
+
+        let l: Deque<u8> = Deque::new();
+        assert!(l.into_sorted_vec().is_empty());
+    }
+
+    #[test]
+    fn fifo_free_list_reuses_oldest_slot_first() {
+        fn reuse_order(mut l: Deque<u8>) -> Vec<usize> {
+            let toks: Vec<Token> = (0..4).map(|i| l.push_back(i)).collect();
+            l.remove(&toks[2]);
+            l.remove(&toks[0]);
+            l.remove(&toks[3]);
+            assert_eq!(Ok(()), l.validate());
+
+            let order = l.freelist_order();
+            let reused: Vec<usize> = (0..3).map(|i| l.push_back(i).ix).collect();
+            assert_eq!(order, reused);
+            assert_eq!(Ok(()), l.validate());
+            reused
+        }
+
+        assert_eq!(vec![3, 0, 2], reuse_order(Deque::new()));
+        assert_eq!(vec![2, 0, 3], reuse_order(Deque::new_fifo()));
+    }
+
+    #[test]
+    fn fifo_free_list_survives_reserve_and_shrink() {
+        let mut l: Deque<u8> = Deque::new_fifo();
+        l.try_reserve_freelist(3).unwrap();
+        assert_eq!(vec![0, 1, 2], l.freelist_order());
+
+        let a = l.push_back(1);
+        l.push_back(2);
+        l.remove(&a);
+        assert_eq!(vec![2, 0], l.freelist_order());
+
+        l.shrink_to(0);
+        assert_eq!(vec![0], l.freelist_order());
+        assert_eq!(Ok(()), l.validate());
+
+        let c = l.clone();
+        assert_eq!(vec![0], c.freelist_order());
+        assert_eq!(Ok(()), c.validate());
+    }
+
+    #[test]
+    fn pop_with_token_returns_original_tokens() {
+        let mut l = Deque::new();
+        let a = l.push_front(1);
+        let b = l.push_back(2);
+        let c = l.push_front(3);
+
+        assert_eq!(Some((c, 3)), l.pop_front_with_token());
+        assert_eq!(Some((b, 2)), l.pop_back_with_token());
+        assert_eq!(Some((a, 1)), l.pop_back_with_token());
+        assert_eq!(None, l.pop_front_with_token());
+        assert_eq!(None, l.pop_back_with_token());
+        assert_eq!(None, l.get(&a));
+    }
+
+    #[test]
+    fn remove_many_removes_each_token_once() {
+        let mut l = Deque::new();
+        let toks: Vec<Token> = (1..=5).map(|i| l.push_back(i)).collect();
+
+        let removed = l.remove_many(&[toks[4], toks[1], toks[4], toks[0]]);
+        assert_eq!(vec![Some(5), Some(2), None, Some(1)], removed);
+        assert_eq!(vec![&3, &4], l.iter_front().collect::<Vec<_>>());
+        assert_eq!(vec![&4, &3], l.iter_back().collect::<Vec<_>>());
+        assert_eq!(Some(&3), l.get_front());
+        assert_eq!(Some(&4), l.get_back());
+        assert_eq!(Ok(()), l.validate());
+
+        assert!(l.remove_many(&[]).is_empty());
+    }
+
+    #[test]
+    fn get_disjoint_mut_rejects_stale_and_aliased_tokens() {
+        let mut l = Deque::new();
+        let toks: Vec<Token> = (1..=4).map(|i| l.push_back(i)).collect();
+
+        match l.get_disjoint_mut(&[&toks[3], &toks[0], &toks[2]]) {
+            Some(v) => {
+                assert_eq!(3, v.len());
+                for (n, i) in v.into_iter().enumerate() {
+                    *i += 10 * (n as u8 + 1);
+                }
+            }
+            None => panic!("disjoint tokens should be accepted"),
+        }
+        assert_eq!(vec![&21, &2, &33, &14], l.iter_front().collect::<Vec<_>>());
+
+        assert_eq!(None, l.get_disjoint_mut(&[&toks[1], &toks[1]]));
+
+        l.remove(&toks[2]);
+        assert_eq!(None, l.get_disjoint_mut(&[&toks[0], &toks[2]]));
+
+        assert_eq!(Some(vec![]), l.get_disjoint_mut(&[]));
+    }
+
+    #[test]
+    fn rotate_to_back_wraps_tail_to_front() {
+        let mut l = Deque::new();
+        let toks: Vec<Token> = (1..=4).map(|i| l.push_back(i)).collect();
+
+        assert!(l.rotate_to_back(&toks[1]));
+        assert_eq!(vec![&3, &4, &1, &2], l.iter_front().collect::<Vec<_>>());
+        assert_eq!(vec![&2, &1, &4, &3], l.iter_back().collect::<Vec<_>>());
+        for (t, i) in toks.iter().zip(1..) {
+            assert_eq!(Some(&i), l.get(t));
+        }
+        assert!(l.is_back(&toks[1]));
+        assert_eq!(Ok(()), l.validate());
+
+        assert!(l.rotate_to_back(&toks[1]));
+        assert_eq!(vec![&3, &4, &1, &2], l.iter_front().collect::<Vec<_>>());
+
+        assert!(l.rotate_to_front(&toks[0]));
+        assert_eq!(vec![&1, &2, &3, &4], l.iter_front().collect::<Vec<_>>());
+        assert_eq!(Ok(()), l.validate());
+
+        l.remove(&toks[2]);
+        assert!(!l.rotate_to_back(&toks[2]));
+        assert!(!l.rotate_to_front(&toks[2]));
+    }
+
+    #[test]
+    fn step_by_front_skips_items() {
+        let l: Deque<u8> = (0..6).collect();
+        assert_eq!(vec![&0, &2, &4], l.step_by_front(2).collect::<Vec<_>>());
+        assert_eq!(vec![&0, &5], l.step_by_front(5).collect::<Vec<_>>());
+        assert_eq!(vec![&0], l.step_by_front(10).collect::<Vec<_>>());
+        assert_eq!(6, l.step_by_front(1).count());
+    }
+
+    #[test]
+    #[should_panic]
+    fn step_by_front_panics_on_zero_step() {
+        let l: Deque<u8> = (0..6).collect();
+        drop(l.step_by_front(0));
+    }
+
+    #[test]
+    fn eq_by_compares_with_predicate() {
+        let close = |a: &f64, b: &f64| (a - b).abs() < 1e-9;
+
+        let a: Deque<f64> = vec![0.1 + 0.2, 1.0, 2.5].into_iter().collect();
+        let b: Deque<f64> = vec![0.3, 1.0, 2.5].into_iter().collect();
+        let c: Deque<f64> = vec![0.3, 1.5, 2.5].into_iter().collect();
+        let d: Deque<f64> = vec![0.3, 1.0].into_iter().collect();
+
+        assert!(a.eq_by(&b, close));
+        assert!(!a.eq_by(&c, close));
+        assert!(!a.eq_by(&d, close));
+        assert!(Deque::<f64>::new().eq_by(&Deque::<f64>::new(), close));
+    }
+
+    #[test]
+    fn zip_map_combines_pairwise() {
+        let a: Deque<u8> = (1..=3).collect();
+        let b: Deque<u8> = vec![10, 20, 30].into_iter().collect();
+
+        let c = a.zip_map(&b, |x, y| x + y);
+        assert_eq!(vec![&11, &22, &33], c.iter_front().collect::<Vec<_>>());
+
+        let d: Deque<u8> = vec![5].into_iter().collect();
+        let e: Deque<String> = a.zip_map(&d, |x, y| format!("{}{}", x, y));
+        assert_eq!(vec!["15"], e.iter_front().collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn first_and_last_token_where_search_from_each_end() {
+        let l: Deque<u8> = (1..=4).collect();
+
+        let first = l.first_token_where(|i| 0 == i % 2).unwrap();
+        let last = l.last_token_where(|i| 0 == i % 2).unwrap();
+        assert_ne!(first, last);
+        assert_eq!(Some(&2), l.get(&first));
+        assert_eq!(Some(&4), l.get(&last));
+
+        assert_eq!(None, l.first_token_where(|i| *i > 4));
+        assert_eq!(None, l.last_token_where(|i| *i > 4));
+    }
+
+    #[test]
+    fn drain_back_into_appends_in_reverse() {
+        let mut l: Deque<u8> = (1..=3).collect();
+        let mut v = Vec::new();
+
+        l.drain_back_into(&mut v);
+        assert_eq!(vec![3, 2, 1], v);
+        assert!(l.is_empty());
+        assert_eq!(3, l.len_freelist());
+        assert_eq!(None, l.get_front());
+        assert_eq!(None, l.get_back());
+        assert_eq!(Ok(()), l.validate());
+    }
+
+    #[test]
+    fn iter_while_front_stops_without_removing() {
+        let l: Deque<u8> = vec![1, 2, 5, 1].into_iter().collect();
+
+        let mut it = l.iter_while_front(|i| *i < 3);
+        assert_eq!(Some(&1), it.next());
+        assert_eq!(Some(&2), it.next());
+        assert_eq!(None, it.next());
+        assert_eq!(None, it.next());
+
+        assert_eq!(vec![&1, &2, &5, &1], l.iter_front().collect::<Vec<_>>());
+        assert_eq!(0, l.iter_while_front(|i| *i > 1).count());
+    }
+
+    #[test]
+    fn position_and_rposition_find_value() {
+        let l: Deque<u8> = vec![1, 2, 3, 2, 1].into_iter().collect();
+
+        assert_eq!(Some(1), l.position(&2));
+        assert_eq!(Some(3), l.rposition(&2));
+        assert_eq!(Some(0), l.position(&1));
+        assert_eq!(Some(4), l.rposition(&1));
+        assert_eq!(Some(2), l.position(&3));
+        assert_eq!(Some(2), l.rposition(&3));
+        assert_eq!(None, l.position(&9));
+        assert_eq!(None, l.rposition(&9));
+    }
+
+    #[test]
+    fn apply_all_mutates_in_order() {
+        let mut l: Deque<u8> = (1..=3).collect();
+        l.apply_all(|i| *i *= *i);
+        assert_eq!(vec![&1, &4, &9], l.iter_front().collect::<Vec<_>>());
+
+        let mut seen = Vec::new();
+        l.apply_all(|i| seen.push(*i));
+        assert_eq!(vec![1, 4, 9], seen);
+    }
+
+    #[test]
+    fn front_back_tokens_resolve_to_endpoints() {
+        let mut l = Deque::new();
+        assert_eq!(None, l.front_back_tokens());
+
+        let a = l.push_back(1);
+        assert_eq!(Some((a, a)), l.front_back_tokens());
+
+        l.push_back(2);
+        let c = l.push_back(3);
+        let z = l.push_front(0);
+        let (f, b) = l.front_back_tokens().unwrap();
+        assert_eq!((z, c), (f, b));
+        assert_eq!(Some(&0), l.get(&f));
+        assert_eq!(Some(&3), l.get(&b));
+    }
+
+    #[test]
+    fn prune_stops_at_sentinel() {
+        let mut l: Deque<u8> = vec![5, 5, 1, 1, 9, 2, 2].into_iter().collect();
+
+        // Remove repeats of the previous kept value until the sentinel.
+        let mut prev = None;
+        l.prune(|i| {
+            if 9 == *i {
+                PruneAction::Stop
+            } else if Some(*i) == prev {
+                PruneAction::Remove
+            } else {
+                prev = Some(*i);
+                PruneAction::Keep
+            }
+        });
+        assert_eq!(vec![&5, &1, &9, &2, &2], l.iter_front().collect::<Vec<_>>());
+
+        let mut seen = 0;
+        l.prune(|_| {
+            seen += 1;
+            if seen > 3 {
+                PruneAction::Stop
+            } else {
+                PruneAction::Remove
+            }
+        });
+        assert_eq!(vec![&2, &2], l.iter_front().collect::<Vec<_>>());
+        assert_eq!(4, seen);
+        assert_eq!(Ok(()), l.validate());
+    }
+
+    #[test]
+    fn capacity_remaining_predicts_growth() {
+        let mut l: Deque<u8> = Deque::with_capacity(8);
+        l.reserve(4);
+        for i in 0..3 {
+            l.push_back(i);
+        }
+        l.pop_front();
+
+        let capacity = l.capacity();
+        let remaining = l.capacity_remaining();
+        assert_eq!(capacity - l.len(), remaining);
+        for i in 0..remaining {
+            l.push_back(i as u8);
+            assert_eq!(capacity, l.capacity());
+        }
+        assert_eq!(0, l.capacity_remaining());
+
+        l.push_back(0);
+        assert!(l.capacity() > capacity);
+    }
+
+    #[test]
+    fn retain_range_only_filters_inside_range() {
+        let mut l = Deque::new();
+        let toks: Vec<Token> = (1..=5).map(|i| l.push_back(i)).collect();
+
+        assert!(l.retain_range(&toks[1], &toks[3], |i| i % 2 == 1));
+        assert_eq!(vec![&1, &3, &5], l.iter_front().collect::<Vec<_>>());
+        assert_eq!(Some(&1), l.get(&toks[0]));
+        assert_eq!(Some(&3), l.get(&toks[2]));
+        assert_eq!(Some(&5), l.get(&toks[4]));
+        assert_eq!(Ok(()), l.validate());
+
+        // The whole deque, including both endpoints.
+        assert!(l.retain_range(&toks[0], &toks[4], |i| *i == 3));
+        assert_eq!(vec![&3], l.iter_front().collect::<Vec<_>>());
+        assert_eq!(Ok(()), l.validate());
+
+        assert!(!l.retain_range(&toks[1], &toks[2], |_| false));
+        assert_eq!(1, l.len());
+    }
+
+    #[test]
+    fn retain_range_rejects_reversed_range() {
+        let mut l = Deque::new();
+        let a = l.push_back(1);
+        let b = l.push_back(2);
 
-        self.len_used += 1;
+        assert!(!l.retain_range(&b, &a, |_| false));
+        assert_eq!(2, l.len());
+    }
 
-        let s = Slot::new_used(front, back, generation, data);
+    #[test]
+    fn concat_joins_parts_in_order() {
+        let parts: Vec<Deque<u8>> = vec![vec![1, 2], vec![3], vec![], vec![4, 5]]
+            .into_iter()
+            .map(|v| v.into_iter().collect())
+            .collect();
 
-        let ix = if usize::MAX == self.free_list {
-            self.slots.push(s);
-            self.slots.len() - 1
-        } else {
-            let ix = self.free_list;
-            self.free_list = self.slots[ix]
-                .get_free()
-                .expect("self.slots[self.free_list] is expected to be free")
-                .next();
-            self.slots[ix] = s;
-            self.len_free -= 1;
-            ix
+        let l = Deque::concat(parts);
+        assert_eq!(vec![&1, &2, &3, &4, &5], l.iter_front().collect::<Vec<_>>());
+        assert!(l.capacity() >= 5);
+        assert_eq!(0, l.len_freelist());
+        assert_eq!(Ok(()), l.validate());
+
+        assert!(Deque::<u8>::concat(Vec::new()).is_empty());
+    }
+
+    #[test]
+    fn try_fold_stops_at_first_error() {
+        let l: Deque<i32> = vec![1, 2, -3, 4, -5].into_iter().collect();
+        let mut seen = Vec::new();
+
+        let sum = |seen: &mut Vec<i32>, acc: i32, i: &i32| {
+            seen.push(*i);
+            if *i < 0 {
+                Err(*i)
+            } else {
+                Ok(acc + i)
+            }
         };
 
-        (ix, generation)
+        assert_eq!(Err(-3), l.try_fold_front(0, |a, i| sum(&mut seen, a, i)));
+        assert_eq!(vec![1, 2, -3], seen);
+
+        seen.clear();
+        assert_eq!(Err(-5), l.try_fold_back(0, |a, i| sum(&mut seen, a, i)));
+        assert_eq!(vec![-5], seen);
+
+        let l: Deque<i32> = (1..=4).collect();
+        assert_eq!(Ok::<_, ()>(10), l.try_fold_front(0, |a, i| Ok(a + i)));
+        assert_eq!(Ok::<_, ()>(10), l.try_fold_back(0, |a, i| Ok(a + i)));
     }
 
-    pub(crate) fn free(&mut self, ix: usize) -> Slot<T> {
-        debug_assert!(self.slots[ix].get_used().is_some());
+    #[test]
+    fn swap_adjacent_moves_item_back_one() {
+        let mut l = Deque::new();
+        let toks: Vec<Token> = (1..=3).map(|i| l.push_back(i)).collect();
 
-        self.len_used -= 1;
+        assert!(l.swap_adjacent(&toks[0]));
+        assert_eq!(vec![&2, &1, &3], l.iter_front().collect::<Vec<_>>());
+        assert_eq!(vec![&3, &1, &2], l.iter_back().collect::<Vec<_>>());
+        assert_eq!(Some(&1), l.get(&toks[0]));
+        assert_eq!(Some(&2), l.get(&toks[1]));
+        assert_eq!(Ok(()), l.validate());
 
-        let mut v = Slot::new_free(self.free_list);
-        std::mem::swap(&mut v, &mut self.slots[ix]);
-        self.free_list = ix;
-        self.len_free += 1;
-        v
+        assert!(l.swap_adjacent(&toks[0]));
+        assert_eq!(vec![&2, &3, &1], l.iter_front().collect::<Vec<_>>());
+        assert!(l.is_back(&toks[0]));
+        assert_eq!(Ok(()), l.validate());
+
+        assert!(!l.swap_adjacent(&toks[0]));
+        l.remove(&toks[1]);
+        assert!(!l.swap_adjacent(&toks[1]));
     }
-}
 
-impl<T> FromIterator<T> for Deque<T> {
-    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
-        let mut l = Self::new();
-        for i in iter {
-            l.push_back(i);
+    #[test]
+    fn retain_from_back_visits_back_first() {
+        fn under_limit() -> impl FnMut(&u8) -> bool {
+            let mut sum = 0;
+            move |i| {
+                sum += *i;
+                sum <= 5
+            }
         }
-        l
-    }
-}
 
-#[cfg(test)]
-mod test {
-    use super::*;
+        let mut front: Deque<u8> = (1..=4).collect();
+        front.retain(under_limit());
+        assert_eq!(vec![&1, &2], front.iter_front().collect::<Vec<_>>());
+
+        let mut back: Deque<u8> = (1..=4).collect();
+        back.retain_from_back(under_limit());
+        assert_eq!(vec![&4], back.iter_front().collect::<Vec<_>>());
+        assert_eq!(Ok(()), back.validate());
+    }
 
     #[test]
-    fn push_get_works() {
-        let mut l = Deque::new();
-        let t10 = l.push_front(10u8);
+    fn parts_round_trip_keeps_tokens() {
+        let mut l = Deque::new_fifo();
+        let toks: Vec<Token> = (1..=5).map(|i| l.push_back(i)).collect();
+        l.remove(&toks[1]);
+        l.remove(&toks[3]);
 
-        let r = l.get(&t10);
-        assert_eq!(Some(&10), r);
-        let r = l.get_front();
-        assert_eq!(Some(&10), r);
-        let r = l.get_back();
-        assert_eq!(Some(&10), r);
+        let parts = l.into_parts();
+        assert_eq!(5, parts.slots.len());
+        assert!(parts.fifo);
 
-        let mut l = Deque::new();
-        let t11 = l.push_back(11u8);
+        let mut l = Deque::from_parts(parts).unwrap();
+        assert_eq!(vec![&1, &3, &5], l.iter_front().collect::<Vec<_>>());
+        assert_eq!(Some(&3), l.get(&toks[2]));
+        assert_eq!(None, l.get(&toks[1]));
+        assert_eq!(vec![1, 3], l.freelist_order());
 
-        let r = l.get(&t11);
-        assert_eq!(Some(&11), r);
-        let r = l.get_front();
-        assert_eq!(Some(&11), r);
-        let r = l.get_back();
-        assert_eq!(Some(&11), r);
+        let t = l.push_back(6);
+        assert!(!toks.contains(&t));
+        assert_eq!(Ok(()), l.validate());
     }
 
     #[test]
-    fn push_remove_works() {
-        let mut l = Deque::new();
-        let t10 = l.push_front(10u8);
+    fn from_parts_rejects_corruption() {
+        let l: Deque<u8> = (1..=3).collect();
 
-        let r = l.remove(&t10);
-        assert_eq!(Some(10), r);
+        let mut parts = l.clone().into_parts();
+        parts.back = parts.front;
+        assert!(Deque::from_parts(parts).is_err());
+
+        let mut parts = l.clone().into_parts();
+        parts.slots[1] = Slot::new_free(usize::MAX);
+        assert!(Deque::from_parts(parts).is_err());
+
+        let mut parts = l.clone().into_parts();
+        parts.len_free = 1;
+        assert!(Deque::from_parts(parts).is_err());
+
+        // Reusing generations would let new tokens match old items.
+        let mut parts = l.into_parts();
+        parts.next_generation = 1;
+        let err = Deque::from_parts(parts).err().unwrap();
+        assert!(err.message().contains("generation"));
+        assert!(err.to_string().contains("generation"));
     }
 
     #[test]
-    fn push_remove_front_get_is_none() {
+    fn stale_tokens_checks_each_token() {
         let mut l = Deque::new();
-        let t10 = l.push_front(10u8);
-
-        let r = l.get_front();
-        assert_eq!(Some(&10), r);
+        let toks: Vec<Token> = (1..=4).map(|i| l.push_back(i)).collect();
+        l.remove(&toks[0]);
+        l.remove(&toks[2]);
 
-        let r = l.pop_front();
-        assert_eq!(Some(10), r);
+        // A new item in a reused slot does not revive the old token.
+        let reused = l.push_back(5);
+        assert_eq!(toks[2].ix, reused.ix);
 
-        let r = l.get(&t10);
-        assert_eq!(None, r);
+        assert_eq!(
+            vec![true, false, true, false, false],
+            l.stale_tokens(&[toks[0], toks[1], toks[2], toks[3], reused])
+        );
+        assert!(!l.contains_token(&toks[2]));
+        assert!(l.contains_token(&reused));
+        assert!(l.stale_tokens(&[]).is_empty());
     }
 
     #[test]
-    fn push_remove_back_get_is_none() {
-        let mut l = Deque::new();
-        let t10 = l.push_front(10u8);
+    fn reserve_freelist_exact_adds_no_slack() {
+        let mut l: Deque<u8> = Deque::new();
+        for i in 0..3 {
+            l.push_back(i);
+        }
 
-        let r = l.get_back();
-        assert_eq!(Some(&10), r);
+        l.reserve_freelist_exact(5);
+        assert_eq!(5, l.len_freelist());
+        assert_eq!(l.len() + 5, l.capacity());
+        assert_eq!(5, l.capacity_remaining());
+        assert_eq!(Ok(()), l.validate());
+    }
 
-        let r = l.pop_back();
-        assert_eq!(Some(10), r);
+    #[test]
+    fn fold_mut_writes_running_sum() {
+        let mut l: Deque<u32> = vec![5, 1, 4, 2].into_iter().collect();
 
-        let r = l.get(&t10);
-        assert_eq!(None, r);
+        let total = l.fold_mut(0, |acc, i| {
+            *i += acc;
+            *i
+        });
+        assert_eq!(12, total);
+        assert_eq!(vec![&5, &6, &10, &12], l.iter_front().collect::<Vec<_>>());
+
+        let mut e: Deque<u32> = Deque::new();
+        assert_eq!(7, e.fold_mut(7, |acc, _| acc + 1));
     }
 
     #[test]
-    fn push_back_works() {
-        let mut l = Deque::new();
-        let t10 = l.push_back(10u8);
-        let t11 = l.push_back(11u8);
+    fn retain_count_reports_removals() {
+        let mut l: Deque<u8> = (1..=6).collect();
+        let free = l.len_freelist();
 
-        let r = l.get(&t10);
-        assert_eq!(Some(&10), r);
-        let r = l.get(&t11);
-        assert_eq!(Some(&11), r);
+        assert_eq!(3, l.retain_count(|i| 1 == i % 2));
+        assert_eq!(vec![&1, &3, &5], l.iter_front().collect::<Vec<_>>());
+        assert_eq!(free + 3, l.len_freelist());
 
-        let r = l.get_front();
-        assert_eq!(Some(&10), r);
-        let r = l.get_back();
-        assert_eq!(Some(&11), r);
+        assert_eq!(0, l.retain_count(|_| true));
+        assert_eq!(3, l.retain_count(|_| false));
+        assert!(l.is_empty());
     }
 
     #[test]
-    fn counts_work_as_expected() {
+    fn is_current_detects_recycled_slots() {
         let mut l = Deque::new();
-        l.push_front(10u8);
-        l.push_front(11u8);
-        assert_eq!(2, l.len());
-        assert_eq!(0, l.len_freelist());
+        let old = l.push_back(1);
+        l.push_back(2);
+        assert_eq!(Some(old.generation), l.slot_generation(old.ix));
 
-        l.pop_back();
-        assert_eq!(1, l.len());
-        assert_eq!(1, l.len_freelist());
+        l.remove(&old);
+        assert_eq!(None, l.slot_generation(old.ix));
+        assert!(!l.is_current(&old));
 
-        l.pop_back();
-        assert_eq!(0, l.len());
-        assert_eq!(2, l.len_freelist());
+        let new = l.push_back(3);
+        assert_eq!(old.ix, new.ix);
+        assert_eq!(Some(new.generation), l.slot_generation(old.ix));
+        assert_ne!(old.generation, new.generation);
+        assert!(!l.is_current(&old));
+        assert!(l.is_current(&new));
+        assert_eq!(None, l.slot_generation(100));
+    }
 
-        l.push_front(12u8);
-        assert_eq!(1, l.len());
-        assert_eq!(1, l.len_freelist());
+    #[test]
+    fn replace_many_overwrites_live_items() {
+        let mut l = Deque::new();
+        let toks: Vec<Token> = (1..=3).map(|i| l.push_back(i)).collect();
+        let stale = l.push_back(4);
+        l.remove(&stale);
 
-        l.push_front(13u8);
-        assert_eq!(2, l.len());
-        assert_eq!(0, l.len_freelist());
+        let old = l.replace_many(&[toks[2], stale, toks[0]], vec![30, 40, 10]);
+        assert_eq!(vec![Some(3), None, Some(1)], old);
+        assert_eq!(vec![&10, &2, &30], l.iter_front().collect::<Vec<_>>());
+        assert_eq!(Some(&30), l.get(&toks[2]));
     }
 
     #[test]
-    fn get_mut_allows_values_to_be_replaced() {
+    #[should_panic]
+    fn replace_many_panics_on_length_mismatch() {
         let mut l = Deque::new();
-        l.push_front(10u8);
-        let t = l.push_front(11u8);
-        l.push_front(12u8);
+        let a = l.push_back(1);
+        l.replace_many(&[a], vec![1, 2]);
+    }
 
-        l.get_mut(&t).map(|v| *v = 20);
+    #[test]
+    fn is_sorted_checks_neighbors() {
+        let sorted: Deque<u8> = vec![1, 2, 2, 5].into_iter().collect();
+        let unsorted: Deque<u8> = vec![1, 3, 2, 5].into_iter().collect();
+        let single: Deque<u8> = vec![7].into_iter().collect();
+        let empty: Deque<u8> = Deque::new();
 
-        let r = l.pop_back();
-        assert_eq!(Some(10), r);
-        let r = l.pop_back();
-        assert_eq!(Some(20), r);
-        let r = l.pop_back();
-        assert_eq!(Some(12), r);
+        assert!(sorted.is_sorted());
+        assert!(!unsorted.is_sorted());
+        assert!(single.is_sorted());
+        assert!(empty.is_sorted());
+
+        let mut calls = 0;
+        assert!(!unsorted.is_sorted_by(|a, b| {
+            calls += 1;
+            a <= b
+        }));
+        assert_eq!(2, calls);
+
+        let floats: Deque<f64> = vec![1.0, f64::NAN].into_iter().collect();
+        assert!(!floats.is_sorted());
     }
 
     #[test]
-    fn can_be_created_from_iterator() {
-        let mut l = Deque::from_iter((0..5).into_iter());
+    fn split_when_splits_before_first_match() {
+        let mut l: Deque<u8> = vec![1, 2, 7, 3, 8].into_iter().collect();
+        let rest = l.split_when(|i| *i > 5).unwrap();
+        assert_eq!(vec![&1, &2], l.iter_front().collect::<Vec<_>>());
+        assert_eq!(vec![&7, &3, &8], rest.iter_front().collect::<Vec<_>>());
+        assert_eq!(Ok(()), l.validate());
+        assert_eq!(Ok(()), rest.validate());
 
-        let r = l.pop_front();
-        assert_eq!(Some(0), r);
-        let r = l.pop_front();
-        assert_eq!(Some(1), r);
-        let r = l.pop_front();
-        assert_eq!(Some(2), r);
-        let r = l.pop_front();
-        assert_eq!(Some(3), r);
-        let r = l.pop_front();
-        assert_eq!(Some(4), r);
+        let mut l: Deque<u8> = vec![9, 1].into_iter().collect();
+        let rest = l.split_when(|i| *i > 5).unwrap();
+        assert!(l.is_empty());
+        assert_eq!(vec![&9, &1], rest.iter_front().collect::<Vec<_>>());
+
+        let mut l: Deque<u8> = (1..=3).collect();
+        assert!(l.split_when(|i| *i > 5).is_none());
+        assert_eq!(vec![&1, &2, &3], l.iter_front().collect::<Vec<_>>());
     }
 
     #[test]
-    fn with_capacity_preallocates_free_list() {
-        let mut l = Deque::with_capacity(3);
-        assert_eq!(3, l.len_freelist());
-        assert_eq!(0, l.len());
+    fn retain_compact_packs_survivors() {
+        let mut l = Deque::new();
+        let toks: Vec<Token> = (0..1000u32).map(|i| l.push_back(i)).collect();
+        for t in toks.iter().step_by(7) {
+            l.remove(t);
+        }
+        l.touch(&toks[500]);
 
-        l.push_front(());
-        assert_eq!(2, l.len_freelist());
-        assert_eq!(1, l.len());
+        let remap = l.retain_compact(|i| 0 == i % 2);
+        assert_eq!(0, l.len_freelist());
+        assert_eq!(l.len(), l.capacity());
+        assert_eq!(remap.len(), l.len());
+        assert_eq!(Ok(()), l.validate());
 
-        // The underlying capacity should not have changed.
-        assert_eq!(3, l.capacity());
+        assert_eq!(Some(&500), l.get_front());
+        for (i, t) in toks.iter().enumerate() {
+            match remap.get(t) {
+                Some(new) => assert_eq!(Some(&(i as u32)), l.get(new)),
+                None => assert!(0 != i % 2 || 0 == i % 7),
+            }
+        }
+        assert_eq!(vec![0, 1, 2, 3], l.debug_layout().chain[..4].to_vec());
 
-        l.push_front(());
-        l.push_front(());
-        l.push_front(());
+        let t = l.push_back(2000);
+        assert!(!remap.values().any(|v| *v == t));
+        assert_eq!(Ok(()), l.validate());
+    }
 
-        assert_eq!(0, l.len_freelist());
-        assert_eq!(4, l.len());
+    #[test]
+    fn endpoint_mut_or_insert_inserts_only_when_empty() {
+        let mut l: Deque<u8> = Deque::new();
+        assert_eq!(&mut 5, l.front_mut_or_insert(|| 5));
+        assert_eq!(1, l.len());
 
-        // The underlying capacity should have expanded to handle 4
-        // items.
-        assert!(3 < l.capacity());
+        l.push_back(9);
+        *l.front_mut_or_insert(|| panic!("should not insert")) += 1;
+        *l.back_mut_or_insert(|| panic!("should not insert")) += 1;
+        assert_eq!(vec![&6, &10], l.iter_front().collect::<Vec<_>>());
+
+        let mut l: Deque<u8> = Deque::new();
+        assert_eq!(&mut 7, l.back_mut_or_insert(|| 7));
+        assert_eq!(Some(&7), l.get_front());
     }
 
     #[test]
-    fn get_front_mut_allows_front_to_change_value() {
+    fn drop_front_while_discards_leading_run() {
         let mut l = Deque::new();
-        l.push_front(10u8);
+        l.push_back(0u8);
+        l.push_back(0);
+        let t = l.push_back(3);
+        l.push_back(0);
 
-        l.get_front_mut().map(|r| *r = 100);
+        assert_eq!(2, l.drop_front_while(|i| 0 == *i));
+        assert_eq!(vec![&3, &0], l.iter_front().collect::<Vec<_>>());
+        assert!(l.is_front(&t));
+        assert_eq!(0, l.drop_front_while(|i| 0 == *i));
 
-        assert_eq!(Some(&100), l.get_front());
+        assert_eq!(2, l.drop_front_while(|_| true));
+        assert!(l.is_empty());
+        assert_eq!(Ok(()), l.validate());
     }
 
     #[test]
-    fn get_back_mut_allows_back_to_change_value() {
+    fn entry_modifies_or_inserts() {
         let mut l = Deque::new();
-        l.push_back(10u8);
+        l.push_back(1);
+        let tok = l.push_back(2);
 
-        l.get_back_mut().map(|r| *r = 100);
+        *l.entry(&tok).and_modify(|i| *i *= 10).or_insert_front(0) += 1;
+        assert_eq!(vec![&1, &21], l.iter_front().collect::<Vec<_>>());
 
-        assert_eq!(Some(&100), l.get_front());
+        l.remove(&tok);
+        let v = l
+            .entry(&tok)
+            .and_modify(|_| panic!("entry is vacant"))
+            .or_insert_front(5);
+        assert_eq!(&mut 5, v);
+        assert_eq!(vec![&5, &1], l.iter_front().collect::<Vec<_>>());
+
+        match l.entry(&tok) {
+            Entry::Vacant(e) => *e.insert_back(7) += 1,
+            Entry::Occupied(_) => panic!("entry should be vacant"),
+        }
+        assert_eq!(vec![&5, &1, &8], l.iter_front().collect::<Vec<_>>());
     }
 
     #[test]
-    fn empty_list() {
-        let mut l: Deque<u8> = Deque::new();
-        let t = l.push_front(1);
-        l.pop_front();
+    fn rotate_by_signed_amount() {
+        let mut l = Deque::new();
+        let toks: Vec<Token> = (1..=4).map(|i| l.push_back(i)).collect();
 
-        assert!(l.is_empty());
+        l.rotate(1);
+        assert_eq!(vec![&2, &3, &4, &1], l.iter_front().collect::<Vec<_>>());
+        assert_eq!(Some(&1), l.get(&toks[0]));
 
-        assert_eq!(None, l.get_front());
-        assert_eq!(None, l.get_front_mut());
+        l.rotate(-1);
+        assert_eq!(vec![&1, &2, &3, &4], l.iter_front().collect::<Vec<_>>());
+        assert_eq!(Some(&2), l.get(&toks[1]));
 
-        assert_eq!(None, l.get_back());
-        assert_eq!(None, l.get_back_mut());
+        l.rotate(5);
+        assert_eq!(vec![&2, &3, &4, &1], l.iter_front().collect::<Vec<_>>());
+        assert_eq!(Some(&4), l.get(&toks[3]));
 
-        assert_eq!(None, l.pop_front());
-        assert_eq!(None, l.pop_back());
+        l.rotate(isize::MIN);
+        l.rotate(0);
+        assert_eq!(vec![&2, &3, &4, &1], l.iter_front().collect::<Vec<_>>());
+        assert_eq!(Ok(()), l.validate());
 
-        assert_eq!(None, l.get(&t));
+        let mut e: Deque<u8> = Deque::new();
+        e.rotate(-3);
+        assert!(e.is_empty());
     }
 
     #[test]
-    fn generation_protects_against_getting_wrong_item() {
-        let mut l: Deque<u8> = Deque::new();
+    fn pairs_front_yields_neighbors() {
+        let l: Deque<i32> = vec![1, 4, 9].into_iter().collect();
+        let diffs: Vec<i32> = l.pairs_front().map(|(a, b)| b - a).collect();
+        assert_eq!(vec![3, 5], diffs);
 
-        // Push in a value, and make sure we can see it.
-        let t0 = l.push_front(1);
-        assert_eq!(Some(&1), l.get(&t0));
-        assert_eq!(Some(&mut 1), l.get_mut(&t0));
+        let single: Deque<i32> = vec![1].into_iter().collect();
+        assert_eq!(0, single.pairs_front().count());
+        assert_eq!(0, Deque::<i32>::new().pairs_front().count());
+    }
 
-        // Pop off the value, and make sure we can no longer see it.
-        l.pop_front();
-        assert_eq!(None, l.get(&t0));
-        assert_eq!(None, l.get_mut(&t0));
+    #[test]
+    fn clear_keeps_generations_unless_reset() {
+        let mut l = Deque::new();
+        let toks: Vec<Token> = (0..3).map(|i| l.push_back(i)).collect();
+        assert_eq!(
+            vec![0, 1, 2],
+            toks.iter().map(|t| t.generation).collect::<Vec<_>>()
+        );
 
-        // Push in a new value, and make sure we can see it.
-        let t1 = l.push_front(2);
-        assert_eq!(Some(&2), l.get(&t1));
-        assert_eq!(Some(&mut 2), l.get_mut(&t1));
+        l.clear();
+        assert!(l.is_empty());
+        assert_eq!(Ok(()), l.validate());
+        let t = l.push_back(3);
+        assert_eq!(3, t.generation);
+        assert!(toks.iter().all(|old| l.get(old).is_none()));
 
-        // Check the original token, and make sure it's still None.
-        assert_eq!(None, l.get(&t0));
-        assert_eq!(None, l.get_mut(&t0));
+        l.clear_reset_generations();
+        assert_eq!(0, l.next_generation());
+        assert_eq!(Ok(()), l.validate());
+        let t = l.push_back(4);
+        assert_eq!(0, t.generation);
+        assert_eq!(1, l.push_back(5).generation);
     }
 
     #[test]
-    fn reserve_increases_capacity() {
-        let mut l: Deque<u8> = Deque::new();
-        l.push_front(1);
-
-        let cap = l.capacity();
-        let res = cap + 16;
+    fn extract_matching_returns_tokens_and_values() {
+        let mut l = Deque::new();
+        let toks: Vec<Token> = (1..=4).map(|i| l.push_back(i)).collect();
 
-        l.reserve(res);
+        let extracted = l.extract_matching(|i| 0 == i % 2);
+        assert_eq!(vec![(toks[1], 2), (toks[3], 4)], extracted);
+        assert_eq!(vec![&1, &3], l.iter_front().collect::<Vec<_>>());
+        assert_eq!(Some(&1), l.get(&toks[0]));
+        assert_eq!(Some(&3), l.get(&toks[2]));
+        assert_eq!(None, l.get(&toks[1]));
+        assert_eq!(Ok(()), l.validate());
 
-        assert!(l.capacity() >= res);
+        assert!(l.extract_matching(|_| false).is_empty());
     }
 
     #[test]
-    fn debug_string() {
-        let mut l: Deque<u8> = Deque::new();
+    fn steal_moves_one_item_between_deques() {
+        let mut a: Deque<u8> = Deque::new();
+        let mut b: Deque<u8> = (1..=3).collect();
 
-        l.push_back(1);
-        l.push_back(2);
-        l.push_back(3);
+        let t1 = a.steal_front_from(&mut b).unwrap();
+        let t3 = a.steal_back_from(&mut b).unwrap();
+        assert_eq!(vec![&3, &1], a.iter_front().collect::<Vec<_>>());
+        assert_eq!(vec![&2], b.iter_front().collect::<Vec<_>>());
+        assert_eq!(Some(&1), a.get(&t1));
+        assert_eq!(Some(&3), a.get(&t3));
+        assert!(a.is_back(&t1));
+        assert!(a.is_front(&t3));
 
-        assert_eq!("[1, 2, 3]", format!("{:?}", l));
+        assert!(a.steal_back_from(&mut b).is_some());
+        assert_eq!(None, a.steal_front_from(&mut b));
+        assert_eq!(None, a.steal_back_from(&mut b));
+        assert_eq!(vec![&2, &3, &1], a.iter_front().collect::<Vec<_>>());
+        assert_eq!(Ok(()), a.validate());
+        assert_eq!(Ok(()), b.validate());
     }
 
     #[test]
-    fn default_works() {
-        let mut l: Deque<u8> = Default::default();
+    fn min_and_max_pick_first_of_ties() {
+        let mut l = Deque::new();
+        let toks: Vec<Token> = vec![3u8, 7, 2, 7, 2]
+            .into_iter()
+            .map(|i| l.push_back(i))
+            .collect();
 
-        l.push_back(1);
-        l.push_back(2);
-        l.push_back(3);
+        assert_eq!(Some(toks[1]), l.max());
+        assert_eq!(Some(toks[2]), l.min());
+        assert_eq!(Some(toks[2]), l.max_by(|a, b| b.cmp(a)));
 
-        assert_eq!(vec![1, 2, 3], l.drain_front().collect::<Vec<u8>>());
+        let max = l.max().unwrap();
+        assert_eq!(Some(7), l.remove(&max));
+        assert_eq!(Some(toks[3]), l.max());
+
+        let e: Deque<u8> = Deque::new();
+        assert_eq!(None, e.min());
+        assert_eq!(None, e.max_by(|a, b| a.cmp(b)));
     }
 }
@@ -0,0 +1,93 @@
+use crate::deque::Deque;
+
+/// A view of the item that a token refers to, which may have been
+/// removed. It is constructed from the [`entry`] method on `Deque`.
+///
+/// [`entry`]: struct.Deque.html#method.entry
+pub enum Entry<'l, T> {
+    /// The token still refers to an item in the deque.
+    Occupied(&'l mut T),
+    /// The item the token referred to has been removed.
+    Vacant(VacantEntry<'l, T>),
+}
+
+impl<'l, T> Entry<'l, T> {
+    /// Get a mutable reference to the item if the entry is occupied.
+    /// Otherwise, push `value` onto the front of the deque and return a
+    /// mutable reference to it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use token_deque::Deque;
+    ///
+    /// let mut l = Deque::new();
+    /// let tok = l.push_back(1);
+    /// l.remove(&tok);
+    ///
+    /// assert_eq!(&mut 2, l.entry(&tok).or_insert_front(2));
+    /// assert_eq!(Some(&2), l.get_front());
+    /// ```
+    pub fn or_insert_front(self, value: T) -> &'l mut T {
+        match self {
+            Entry::Occupied(v) => v,
+            Entry::Vacant(e) => e.insert_front(value),
+        }
+    }
+
+    /// Call `f` on the item if the entry is occupied, and return the
+    /// entry.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use token_deque::Deque;
+    ///
+    /// let mut l = Deque::new();
+    /// let tok = l.push_back(1);
+    ///
+    /// l.entry(&tok).and_modify(|i| *i += 10);
+    /// assert_eq!(Some(&11), l.get(&tok));
+    /// ```
+    pub fn and_modify<F: FnOnce(&mut T)>(self, f: F) -> Self {
+        match self {
+            Entry::Occupied(v) => {
+                f(v);
+                Entry::Occupied(v)
+            }
+            Entry::Vacant(e) => Entry::Vacant(e),
+        }
+    }
+}
+
+/// The vacant case of an [`Entry`]. It can be used to insert a new
+/// item into the deque.
+///
+/// [`Entry`]: enum.Entry.html
+pub struct VacantEntry<'l, T> {
+    target: &'l mut Deque<T>,
+}
+
+impl<'l, T> VacantEntry<'l, T> {
+    pub(crate) fn new(target: &'l mut Deque<T>) -> Self {
+        Self { target }
+    }
+
+    /// Push `value` onto the front of the deque and return a mutable
+    /// reference to it.
+    pub fn insert_front(self, value: T) -> &'l mut T {
+        self.target.push_front(value);
+        self.target
+            .get_front_mut()
+            .expect("the deque is known to be non-empty")
+    }
+
+    /// Push `value` onto the back of the deque and return a mutable
+    /// reference to it.
+    pub fn insert_back(self, value: T) -> &'l mut T {
+        self.target.push_back(value);
+        self.target
+            .get_back_mut()
+            .expect("the deque is known to be non-empty")
+    }
+}
@@ -0,0 +1,31 @@
+use std::error::Error;
+use std::fmt;
+
+/// An error returned when a `Deque` can not be built from its parts.
+/// It is returned by the [`from_parts`] method on `Deque`, and
+/// describes the first inconsistency that was found.
+///
+/// [`from_parts`]: struct.Deque.html#method.from_parts
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DequeError {
+    message: String,
+}
+
+impl DequeError {
+    pub(crate) fn new(message: String) -> Self {
+        Self { message }
+    }
+
+    /// A description of the problem.
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+}
+
+impl fmt::Display for DequeError {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(fmt, "inconsistent deque: {}", self.message)
+    }
+}
+
+impl Error for DequeError {}
@@ -1,5 +1,5 @@
 use crate::deque::Deque;
-use std::usize;
+use crate::token::Token;
 
 /// An iterator over the deque starting from the front. It is
 /// constructed from the [`iter_front`] method on `Deque`.
@@ -14,8 +14,34 @@ impl<'l, T> IterFront<'l, T> {
     pub(crate) fn new(target: &'l Deque<T>, next_index: usize) -> Self {
         Self { target, next_index }
     }
+
+    /// Turn this into an iterator that starts from the back of the
+    /// deque. This starts over from the far end, rather than reversing
+    /// the items that have not been yielded yet.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use token_deque::Deque;
+    ///
+    /// let d: Deque<u8> = (1..=3).collect();
+    ///
+    /// let v: Vec<&u8> = d.iter_front().rev().collect();
+    /// assert_eq!(vec![&3, &2, &1], v);
+    /// ```
+    pub fn rev(self) -> IterBack<'l, T> {
+        self.target.iter_back()
+    }
+}
+
+impl<'l, T> Clone for IterFront<'l, T> {
+    fn clone(&self) -> Self {
+        *self
+    }
 }
 
+impl<'l, T> Copy for IterFront<'l, T> {}
+
 impl<'l, T> Iterator for IterFront<'l, T> {
     type Item = &'l T;
 
@@ -45,8 +71,34 @@ impl<'l, T> IterBack<'l, T> {
     pub(crate) fn new(target: &'l Deque<T>, next_index: usize) -> Self {
         Self { target, next_index }
     }
+
+    /// Turn this into an iterator that starts from the front of the
+    /// deque. This starts over from the far end, rather than reversing
+    /// the items that have not been yielded yet.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use token_deque::Deque;
+    ///
+    /// let d: Deque<u8> = (1..=3).collect();
+    ///
+    /// let v: Vec<&u8> = d.iter_back().rev().collect();
+    /// assert_eq!(vec![&1, &2, &3], v);
+    /// ```
+    pub fn rev(self) -> IterFront<'l, T> {
+        self.target.iter_front()
+    }
 }
 
+impl<'l, T> Clone for IterBack<'l, T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<'l, T> Copy for IterBack<'l, T> {}
+
 impl<'l, T> Iterator for IterBack<'l, T> {
     type Item = &'l T;
 
@@ -83,13 +135,12 @@ impl<'l, T> Iterator for DrainFront<'l, T> {
 
     fn next(&mut self) -> Option<Self::Item> {
         if usize::MAX != self.next_index {
-            let r = self.target.free(self.next_index);
-            let (_, value, back) = r
-                .into_used()
+            let ix = self.next_index;
+            self.next_index = self.target.slots[ix]
+                .get_used()
                 .expect("self.target.slots[self.next_index] is expected to be used")
-                .take();
-            self.next_index = back;
-            Some(value)
+                .back();
+            Some(self.target.remove_unchecked(ix))
         } else {
             None
         }
@@ -116,17 +167,354 @@ impl<'l, T> Iterator for DrainBack<'l, T> {
 
     fn next(&mut self) -> Option<Self::Item> {
         if usize::MAX != self.next_index {
-            let r = self.target.free(self.next_index);
-            let (front, value, _) = r
-                .into_used()
+            let ix = self.next_index;
+            self.next_index = self.target.slots[ix]
+                .get_used()
                 .expect("self.target.slots[self.next_index] is expected to be used")
-                .take();
-            self.next_index = front;
-            Some(value)
+                .front();
+            Some(self.target.remove_unchecked(ix))
+        } else {
+            None
+        }
+    }
+}
+
+/// A draining iterator over a range of the deque, from front to back.
+/// It is constructed from the [`drain_range`] method on `Deque`. Any
+/// items in the range that have not been yielded when the iterator is
+/// dropped are removed from the deque anyway.
+///
+/// [`drain_range`]: struct.Deque.html#method.drain_range
+pub struct DrainRange<'l, T> {
+    target: &'l mut Deque<T>,
+    next_index: usize,
+    last_index: usize,
+}
+
+impl<'l, T> DrainRange<'l, T> {
+    pub(crate) fn new(target: &'l mut Deque<T>, next_index: usize, last_index: usize) -> Self {
+        Self {
+            target,
+            next_index,
+            last_index,
+        }
+    }
+}
+
+impl<'l, T> Iterator for DrainRange<'l, T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if usize::MAX != self.next_index {
+            let ix = self.next_index;
+            self.next_index = if self.last_index == ix {
+                usize::MAX
+            } else {
+                self.target.slots[ix]
+                    .get_used()
+                    .expect("self.target.slots[self.next_index] is expected to be used")
+                    .back()
+            };
+            Some(self.target.remove_unchecked(ix))
+        } else {
+            None
+        }
+    }
+}
+
+impl<'l, T> Drop for DrainRange<'l, T> {
+    fn drop(&mut self) {
+        for _ in self {}
+    }
+}
+
+/// A draining iterator over the front of the deque that stops at the
+/// first item that does not satisfy a predicate. It is constructed
+/// from the [`drain_front_while`] method on `Deque`.
+///
+/// [`drain_front_while`]: struct.Deque.html#method.drain_front_while
+pub struct DrainFrontWhile<'l, T, F> {
+    target: &'l mut Deque<T>,
+    pred: F,
+    done: bool,
+}
+
+impl<'l, T, F> DrainFrontWhile<'l, T, F> {
+    pub(crate) fn new(target: &'l mut Deque<T>, pred: F) -> Self {
+        Self {
+            target,
+            pred,
+            done: false,
+        }
+    }
+}
+
+impl<'l, T, F> Iterator for DrainFrontWhile<'l, T, F>
+where
+    F: FnMut(&T) -> bool,
+{
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if !self.done && self.target.get_front().is_some_and(&mut self.pred) {
+            self.target.pop_front()
+        } else {
+            self.done = true;
+            None
+        }
+    }
+}
+
+/// A draining iterator over the back of the deque that stops at the
+/// first item that does not satisfy a predicate. It is constructed
+/// from the [`drain_back_while`] method on `Deque`.
+///
+/// [`drain_back_while`]: struct.Deque.html#method.drain_back_while
+pub struct DrainBackWhile<'l, T, F> {
+    target: &'l mut Deque<T>,
+    pred: F,
+    done: bool,
+}
+
+impl<'l, T, F> DrainBackWhile<'l, T, F> {
+    pub(crate) fn new(target: &'l mut Deque<T>, pred: F) -> Self {
+        Self {
+            target,
+            pred,
+            done: false,
+        }
+    }
+}
+
+impl<'l, T, F> Iterator for DrainBackWhile<'l, T, F>
+where
+    F: FnMut(&T) -> bool,
+{
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if !self.done && self.target.get_back().is_some_and(&mut self.pred) {
+            self.target.pop_back()
+        } else {
+            self.done = true;
+            None
+        }
+    }
+}
+
+/// A draining iterator over at most `n` items from the front of the
+/// deque. It is constructed from the [`drain_front_n`] method on
+/// `Deque`. Any of the `n` items not yet yielded are removed when the
+/// iterator is dropped.
+///
+/// [`drain_front_n`]: struct.Deque.html#method.drain_front_n
+pub struct DrainFrontN<'l, T> {
+    target: &'l mut Deque<T>,
+    remaining: usize,
+}
+
+impl<'l, T> DrainFrontN<'l, T> {
+    pub(crate) fn new(target: &'l mut Deque<T>, n: usize) -> Self {
+        let remaining = n.min(target.len());
+        Self { target, remaining }
+    }
+}
+
+impl<'l, T> Iterator for DrainFrontN<'l, T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if 0 != self.remaining {
+            self.remaining -= 1;
+            self.target.pop_front()
+        } else {
+            None
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl<'l, T> Drop for DrainFrontN<'l, T> {
+    fn drop(&mut self) {
+        for _ in self {}
+    }
+}
+
+/// A draining iterator over at most `n` items from the back of the
+/// deque. It is constructed from the [`drain_back_n`] method on
+/// `Deque`. Any of the `n` items not yet yielded are removed when the
+/// iterator is dropped.
+///
+/// [`drain_back_n`]: struct.Deque.html#method.drain_back_n
+pub struct DrainBackN<'l, T> {
+    target: &'l mut Deque<T>,
+    remaining: usize,
+}
+
+impl<'l, T> DrainBackN<'l, T> {
+    pub(crate) fn new(target: &'l mut Deque<T>, n: usize) -> Self {
+        let remaining = n.min(target.len());
+        Self { target, remaining }
+    }
+}
+
+impl<'l, T> Iterator for DrainBackN<'l, T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if 0 != self.remaining {
+            self.remaining -= 1;
+            self.target.pop_back()
+        } else {
+            None
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl<'l, T> Drop for DrainBackN<'l, T> {
+    fn drop(&mut self) {
+        for _ in self {}
+    }
+}
+
+// An iterator over the deque from front to back that starts over from
+// the front a fixed number of times.
+pub(crate) struct IterCycle<'l, T> {
+    target: &'l Deque<T>,
+    next_index: usize,
+    // The number of passes left to start after the current one.
+    cycles: usize,
+    // The number of items left to yield, if it fits in a `usize`.
+    remaining: Option<usize>,
+}
+
+impl<'l, T> IterCycle<'l, T> {
+    pub(crate) fn new(target: &'l Deque<T>, cycles: usize) -> Self {
+        let remaining = target.len().checked_mul(cycles);
+        let (next_index, cycles) = if target.is_empty() || 0 == cycles {
+            (usize::MAX, 0)
+        } else {
+            (target.front, cycles - 1)
+        };
+
+        Self {
+            target,
+            next_index,
+            cycles,
+            remaining,
+        }
+    }
+}
+
+impl<'l, T> Iterator for IterCycle<'l, T> {
+    type Item = &'l T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if usize::MAX == self.next_index && 0 != self.cycles {
+            self.cycles -= 1;
+            self.next_index = self.target.front;
+        }
+
+        if usize::MAX != self.next_index {
+            let r = self.target.slots[self.next_index]
+                .get_used()
+                .expect("self.target.slots[self.next_index] is expected to be used");
+            self.next_index = r.back();
+            self.remaining = self.remaining.map(|n| n - 1);
+            Some(r.data())
         } else {
             None
         }
     }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        match self.remaining {
+            Some(n) => (n, Some(n)),
+            None => (usize::MAX, None),
+        }
+    }
+}
+
+// An iterator that yields the items of another iterator over the deque
+// in groups of `size`. The last group may be smaller.
+pub(crate) struct Chunks<I> {
+    iter: I,
+    size: usize,
+}
+
+impl<I> Chunks<I> {
+    pub(crate) fn new(iter: I, size: usize) -> Self {
+        assert!(0 != size, "chunk size must be non-zero");
+
+        Self { iter, size }
+    }
+}
+
+impl<I: Iterator> Iterator for Chunks<I> {
+    type Item = Vec<I::Item>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let chunk: Vec<I::Item> = self.iter.by_ref().take(self.size).collect();
+        if chunk.is_empty() {
+            None
+        } else {
+            Some(chunk)
+        }
+    }
+}
+
+// An iterator over the deque from front to back that yields each
+// item's position and token along with the item.
+pub(crate) struct IterPositioned<'l, T> {
+    target: &'l Deque<T>,
+    next_index: usize,
+    position: usize,
+}
+
+impl<'l, T> IterPositioned<'l, T> {
+    pub(crate) fn new(target: &'l Deque<T>) -> Self {
+        Self {
+            target,
+            next_index: target.front,
+            position: 0,
+        }
+    }
+}
+
+impl<'l, T> Iterator for IterPositioned<'l, T> {
+    type Item = (usize, Token, &'l T);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if usize::MAX != self.next_index {
+            let ix = self.next_index;
+            let r = self.target.slots[ix]
+                .get_used()
+                .expect("self.target.slots[self.next_index] is expected to be used");
+            let token = Token {
+                ix,
+                generation: r.generation(),
+            };
+            let position = self.position;
+
+            self.next_index = r.back();
+            self.position += 1;
+            Some((position, token, r.data()))
+        } else {
+            None
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let n = self.target.len() - self.position;
+        (n, Some(n))
+    }
 }
 
 #[cfg(test)]
@@ -140,10 +528,10 @@ mod test {
         l.push_front(11u8);
         l.push_front(12u8);
 
-        assert_eq!(Some(&10), l.iter_front().filter(|i| **i == 10).next());
-        assert_eq!(Some(&11), l.iter_front().filter(|i| **i == 11).next());
-        assert_eq!(Some(&12), l.iter_front().filter(|i| **i == 12).next());
-        assert_eq!(None, l.iter_front().filter(|i| **i == 13).next());
+        assert_eq!(Some(&10), l.iter_front().find(|i| **i == 10));
+        assert_eq!(Some(&11), l.iter_front().find(|i| **i == 11));
+        assert_eq!(Some(&12), l.iter_front().find(|i| **i == 12));
+        assert_eq!(None, l.iter_front().find(|i| **i == 13));
     }
 
     #[test]
@@ -206,4 +594,295 @@ mod test {
         assert_eq!(vec![10, 11, 12], l.drain_back().collect::<Vec<u8>>());
         assert_eq!(3, l.len_freelist());
     }
+
+    #[test]
+    fn drain_range_removes_interior_range() {
+        let mut l = Deque::new();
+        let t1 = l.push_back(1u8);
+        let t2 = l.push_back(2u8);
+        l.push_back(3u8);
+        let t4 = l.push_back(4u8);
+        let t5 = l.push_back(5u8);
+
+        let v: Vec<u8> = l.drain_range(&t2, &t4).unwrap().collect();
+        assert_eq!(vec![2, 3, 4], v);
+        assert_eq!(vec![&1, &5], l.iter_front().collect::<Vec<&u8>>());
+        assert_eq!(vec![&5, &1], l.iter_back().collect::<Vec<&u8>>());
+        assert_eq!(Some(&1), l.get(&t1));
+        assert_eq!(Some(&5), l.get(&t5));
+        assert_eq!(3, l.len_freelist());
+    }
+
+    #[test]
+    fn drain_range_can_drain_everything() {
+        let mut l = Deque::new();
+        let t1 = l.push_back(1u8);
+        l.push_back(2u8);
+        let t3 = l.push_back(3u8);
+
+        let v: Vec<u8> = l.drain_range(&t1, &t3).unwrap().collect();
+        assert_eq!(vec![1, 2, 3], v);
+        assert!(l.is_empty());
+        assert_eq!(None, l.get_front());
+        assert_eq!(None, l.get_back());
+
+        l.push_front(4u8);
+        assert_eq!(vec![&4], l.iter_front().collect::<Vec<&u8>>());
+    }
+
+    #[test]
+    fn drain_range_rejects_reversed_range() {
+        let mut l = Deque::new();
+        let t1 = l.push_back(1u8);
+        l.push_back(2u8);
+        let t3 = l.push_back(3u8);
+
+        assert!(l.drain_range(&t3, &t1).is_none());
+        assert_eq!(3, l.len());
+    }
+
+    #[test]
+    fn drain_range_finishes_when_dropped() {
+        let mut l = Deque::new();
+        let t1 = l.push_back(1u8);
+        let t2 = l.push_back(2u8);
+        l.push_back(3u8);
+        let t4 = l.push_back(4u8);
+
+        {
+            let mut d = l.drain_range(&t2, &t4).unwrap();
+            assert_eq!(Some(2), d.next());
+        }
+
+        assert_eq!(vec![&1], l.iter_front().collect::<Vec<&u8>>());
+        assert_eq!(Some(&1), l.get(&t1));
+    }
+
+    #[test]
+    fn cloned_iters_advance_independently() {
+        let l: Deque<u8> = (1..=4).collect();
+
+        let mut a = l.iter_front();
+        assert_eq!(Some(&1), a.next());
+        let mut b = a;
+        assert_eq!(Some(&2), a.next());
+        assert_eq!(vec![&3, &4], a.collect::<Vec<&u8>>());
+        assert_eq!(vec![&2, &3, &4], b.by_ref().collect::<Vec<&u8>>());
+        assert_eq!(None, b.next());
+
+        let mut a = l.iter_back();
+        assert_eq!(Some(&4), a.next());
+        let b = a;
+        assert_eq!(vec![&3, &2, &1], a.collect::<Vec<&u8>>());
+        assert_eq!(vec![&3, &2, &1], b.collect::<Vec<&u8>>());
+    }
+
+    #[test]
+    fn drain_front_while_stops_at_first_failure() {
+        let mut l: Deque<u8> = vec![1, 2, 5, 1].into_iter().collect();
+
+        assert_eq!(
+            vec![1, 2],
+            l.drain_front_while(|i| *i < 3).collect::<Vec<u8>>()
+        );
+        assert_eq!(vec![&5, &1], l.iter_front().collect::<Vec<&u8>>());
+        assert_eq!(vec![&1, &5], l.iter_back().collect::<Vec<&u8>>());
+        assert_eq!(2, l.len_freelist());
+        assert_eq!(Ok(()), l.validate());
+
+        assert_eq!(None, l.drain_front_while(|i| *i < 3).next());
+        assert_eq!(2, l.len());
+    }
+
+    #[test]
+    fn drain_back_while_stops_at_first_failure() {
+        let mut l: Deque<u8> = vec![1, 5, 2, 1].into_iter().collect();
+
+        assert_eq!(
+            vec![1, 2],
+            l.drain_back_while(|i| *i < 3).collect::<Vec<u8>>()
+        );
+        assert_eq!(vec![&1, &5], l.iter_front().collect::<Vec<&u8>>());
+        assert_eq!(Ok(()), l.validate());
+
+        assert_eq!(
+            vec![5, 1],
+            l.drain_back_while(|_| true).collect::<Vec<u8>>()
+        );
+        assert!(l.is_empty());
+        assert_eq!(Ok(()), l.validate());
+    }
+
+    #[test]
+    fn pop_n_removes_at_most_n() {
+        let mut l: Deque<u8> = (1..=5).collect();
+        assert_eq!(vec![1, 2], l.pop_front_n(2).collect::<Vec<u8>>());
+        assert_eq!(vec![5, 4], l.pop_back_n(2).collect::<Vec<u8>>());
+        assert_eq!(vec![&3], l.iter_front().collect::<Vec<&u8>>());
+
+        assert_eq!(vec![3], l.pop_front_n(10).collect::<Vec<u8>>());
+        assert!(l.is_empty());
+        assert_eq!(5, l.len_freelist());
+        assert_eq!(Ok(()), l.validate());
+
+        let mut l: Deque<u8> = (1..=3).collect();
+        assert_eq!(vec![3, 2, 1], l.pop_back_n(10).collect::<Vec<u8>>());
+        assert!(l.is_empty());
+        assert_eq!(3, l.len_freelist());
+    }
+
+    #[test]
+    fn pop_n_finishes_when_dropped() {
+        let mut l: Deque<u8> = (1..=5).collect();
+        {
+            let mut p = l.pop_front_n(3);
+            assert_eq!((3, Some(3)), p.size_hint());
+            assert_eq!(Some(1), p.next());
+        }
+        assert_eq!(vec![&4, &5], l.iter_front().collect::<Vec<&u8>>());
+
+        drop(l.pop_back_n(1));
+        assert_eq!(vec![&4], l.iter_front().collect::<Vec<&u8>>());
+        assert_eq!(Ok(()), l.validate());
+    }
+
+    #[test]
+    fn iter_cycle_repeats_a_bounded_number_of_times() {
+        let l: Deque<u8> = vec![1, 2].into_iter().collect();
+
+        let mut c = l.iter_cycle(3);
+        assert_eq!((6, Some(6)), c.size_hint());
+        c.next();
+        c.next();
+        c.next();
+        assert_eq!((3, Some(3)), c.size_hint());
+        assert_eq!(vec![&2, &1, &2], c.collect::<Vec<&u8>>());
+
+        assert_eq!(0, l.iter_cycle(0).count());
+        assert_eq!((0, Some(0)), l.iter_cycle(0).size_hint());
+        assert_eq!((2, Some(2)), l.iter_cycle(1).size_hint());
+        assert_eq!(vec![&1, &2], l.iter_cycle(1).collect::<Vec<&u8>>());
+
+        let e: Deque<u8> = Deque::new();
+        assert_eq!((0, Some(0)), e.iter_cycle(usize::MAX).size_hint());
+        assert_eq!(None, e.iter_cycle(usize::MAX).next());
+
+        assert_eq!((usize::MAX, None), l.iter_cycle(usize::MAX).size_hint());
+    }
+
+    #[test]
+    fn chunks_front_groups_items() {
+        let l: Deque<u8> = (1..=5).collect();
+        let v: Vec<Vec<&u8>> = l.chunks_front(2).collect();
+        assert_eq!(vec![vec![&1, &2], vec![&3, &4], vec![&5]], v);
+
+        let v: Vec<Vec<&u8>> = l.chunks_front(5).collect();
+        assert_eq!(vec![vec![&1, &2, &3, &4, &5]], v);
+
+        let e: Deque<u8> = Deque::new();
+        assert_eq!(0, e.chunks_front(3).count());
+    }
+
+    #[test]
+    #[should_panic]
+    fn chunks_front_panics_on_zero_size() {
+        let l: Deque<u8> = (1..=5).collect();
+        drop(l.chunks_front(0));
+    }
+
+    #[test]
+    fn rev_starts_over_from_far_end() {
+        let l: Deque<u8> = (1..=4).collect();
+
+        assert_eq!(
+            l.iter_back().collect::<Vec<_>>(),
+            l.iter_front().rev().collect::<Vec<_>>()
+        );
+        assert_eq!(
+            l.iter_front().collect::<Vec<_>>(),
+            l.iter_back().rev().collect::<Vec<_>>()
+        );
+
+        let mut it = l.iter_front();
+        it.next();
+        it.next();
+        assert_eq!(4, it.rev().count());
+    }
+
+    #[test]
+    fn chunks_back_groups_items_from_back() {
+        let l: Deque<u8> = (1..=5).collect();
+        let v: Vec<Vec<&u8>> = l.chunks_back(2).collect();
+        assert_eq!(vec![vec![&5, &4], vec![&3, &2], vec![&1]], v);
+
+        let v: Vec<Vec<&u8>> = l.chunks_back(3).collect();
+        assert_eq!(vec![vec![&5, &4, &3], vec![&2, &1]], v);
+    }
+
+    #[test]
+    #[should_panic]
+    fn chunks_back_panics_on_zero_size() {
+        let l: Deque<u8> = (1..=5).collect();
+        drop(l.chunks_back(0));
+    }
+
+    #[test]
+    fn drain_front_n_leaves_rest_consistent() {
+        let mut l = Deque::new();
+        let toks: Vec<_> = (1..=5).map(|i| l.push_back(i)).collect();
+
+        let mut d = l.drain_front_n(2);
+        assert_eq!((2, Some(2)), d.size_hint());
+        assert_eq!(Some(1), d.next());
+        drop(d);
+
+        assert_eq!(vec![&3, &4, &5], l.iter_front().collect::<Vec<_>>());
+        assert_eq!(vec![&5, &4, &3], l.iter_back().collect::<Vec<_>>());
+        assert!(l.is_front(&toks[2]));
+        assert!(l.is_back(&toks[4]));
+        assert_eq!(2, l.len_freelist());
+        assert_eq!(Ok(()), l.validate());
+
+        assert_eq!(vec![3, 4, 5], l.drain_front_n(10).collect::<Vec<_>>());
+        assert!(l.is_empty());
+        assert_eq!(Ok(()), l.validate());
+    }
+
+    #[test]
+    fn drain_back_n_leaves_rest_consistent() {
+        let mut l = Deque::new();
+        let toks: Vec<_> = (1..=5).map(|i| l.push_back(i)).collect();
+
+        let mut d = l.drain_back_n(2);
+        assert_eq!((2, Some(2)), d.size_hint());
+        assert_eq!(Some(5), d.next());
+        drop(d);
+
+        assert_eq!(vec![&1, &2, &3], l.iter_front().collect::<Vec<_>>());
+        assert_eq!(vec![&3, &2, &1], l.iter_back().collect::<Vec<_>>());
+        assert!(l.is_front(&toks[0]));
+        assert!(l.is_back(&toks[2]));
+        assert_eq!(2, l.len_freelist());
+        assert_eq!(Ok(()), l.validate());
+
+        assert_eq!(vec![3, 2, 1], l.drain_back_n(10).collect::<Vec<_>>());
+        assert!(l.is_empty());
+        assert_eq!(Ok(()), l.validate());
+    }
+
+    #[test]
+    fn iter_front_positioned_yields_positions_and_tokens() {
+        let mut l = Deque::new();
+        let b = l.push_back(20u8);
+        let c = l.push_back(30);
+        let a = l.push_front(10);
+
+        let v: Vec<(usize, Token, &u8)> = l.iter_front_positioned().collect();
+        assert_eq!(vec![(0, a, &10), (1, b, &20), (2, c, &30)], v);
+        assert_eq!((3, Some(3)), l.iter_front_positioned().size_hint());
+
+        for (_, t, i) in l.iter_front_positioned() {
+            assert_eq!(Some(i), l.get(&t));
+        }
+    }
 }
@@ -0,0 +1,28 @@
+/// A read-only snapshot of the internal layout of a `Deque`. It is
+/// constructed from the [`debug_layout`] method on `Deque`, and is
+/// meant for tests and tools that need to see how items are arranged
+/// in the underlying `Vec`.
+///
+/// Indices refer to slots in the underlying `Vec`. Where an index may
+/// be missing, `usize::MAX` is used, matching the deque's own internal
+/// representation.
+///
+/// [`debug_layout`]: struct.Deque.html#method.debug_layout
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DebugLayout {
+    /// The index of the front of the deque. MAX when the deque is
+    /// empty.
+    pub front: usize,
+    /// The index of the back of the deque. MAX when the deque is
+    /// empty.
+    pub back: usize,
+    /// The index of the first slot on the free list. MAX when the
+    /// free list is empty.
+    pub free_list: usize,
+    /// The number of slots currently used by items.
+    pub len_used: usize,
+    /// The number of slots currently on the free list.
+    pub len_free: usize,
+    /// The index of every used slot, from front to back.
+    pub chain: Vec<usize>,
+}
@@ -9,10 +9,23 @@
 //! are inserted into the list before the internal `Vec` is expanded.
 
 mod deque;
+mod entry;
+mod error;
 mod iterators;
+mod layout;
+pub mod parts;
+mod prune;
 mod slot;
 mod token;
 
 pub use crate::deque::Deque;
-pub use crate::iterators::{DrainBack, DrainFront, IterBack, IterFront};
+pub use crate::entry::{Entry, VacantEntry};
+pub use crate::error::DequeError;
+pub use crate::iterators::{
+    DrainBack, DrainBackN, DrainBackWhile, DrainFront, DrainFrontN, DrainFrontWhile, DrainRange,
+    IterBack, IterFront,
+};
+pub use crate::layout::DebugLayout;
+pub use crate::parts::DequeParts;
+pub use crate::prune::PruneAction;
 pub use crate::token::Token;
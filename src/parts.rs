@@ -0,0 +1,55 @@
+//! The raw layout of a `Deque`, for callers that need to move its
+//! backing storage in and out with [`into_parts`] and [`from_parts`].
+//!
+//! Everything in this module mirrors the deque's internals and is not
+//! covered by the usual stability guarantees. It may change between
+//! versions.
+//!
+//! [`into_parts`]: ../struct.Deque.html#method.into_parts
+//! [`from_parts`]: ../struct.Deque.html#method.from_parts
+
+pub use crate::slot::{Free, Slot, Used};
+
+/// The raw parts of a `Deque`: the `Vec` of slots that backs it and
+/// the bookkeeping that goes with it. It is constructed from the
+/// [`into_parts`] method on `Deque`, and turned back into a deque with
+/// [`from_parts`], without copying the slots.
+///
+/// Indices refer to slots in `slots`. Where an index may be missing,
+/// `usize::MAX` is used, matching the deque's own internal
+/// representation.
+///
+/// This mirrors the deque's internal layout, including details such as
+/// `free_tail` and `fifo`, and is not covered by the usual stability
+/// guarantees: fields may be added, removed, or change meaning between
+/// versions. Code using it should expect to be updated along with
+/// the crate.
+///
+/// [`into_parts`]: ../struct.Deque.html#method.into_parts
+/// [`from_parts`]: ../struct.Deque.html#method.from_parts
+#[derive(Clone)]
+pub struct DequeParts<T> {
+    /// The memory used to back the deque.
+    pub slots: Vec<Slot<T>>,
+    /// The index of the first slot on the free list. MAX when the
+    /// free list is empty.
+    pub free_list: usize,
+    /// The index of the last slot on the free list. MAX when the free
+    /// list is empty.
+    pub free_tail: usize,
+    /// Whether freed slots go on the tail of the free list, rather
+    /// than the head.
+    pub fifo: bool,
+    /// The index of the front of the deque. MAX when the deque is
+    /// empty.
+    pub front: usize,
+    /// The index of the back of the deque. MAX when the deque is
+    /// empty.
+    pub back: usize,
+    /// The generation that will be given to the next item pushed.
+    pub next_generation: usize,
+    /// The number of slots currently used by items.
+    pub len_used: usize,
+    /// The number of slots currently on the free list.
+    pub len_free: usize,
+}
@@ -0,0 +1,13 @@
+/// What [`prune`] should do with the item it has just shown to its
+/// callback.
+///
+/// [`prune`]: struct.Deque.html#method.prune
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PruneAction {
+    /// Leave the item in the deque and move on to the next one.
+    Keep,
+    /// Remove the item from the deque and move on to the next one.
+    Remove,
+    /// Leave the item in the deque and end the walk.
+    Stop,
+}
@@ -1,4 +1,10 @@
-pub(crate) struct Free(FreeInner);
+/// A slot on the free list of a `Deque`. Like [`Slot`], this is part of
+/// the unstable raw layout and may change between versions.
+///
+/// [`Slot`]: enum.Slot.html
+#[derive(Clone)]
+pub struct Free(FreeInner);
+#[derive(Clone)]
 pub(crate) struct FreeInner {
     // The next free slot.
     next: usize,
@@ -9,12 +15,24 @@ impl Free {
         Free(FreeInner { next })
     }
 
-    pub(crate) fn next(&self) -> usize {
+    /// The index of the next slot on the free list. MAX at the end of
+    /// the free list.
+    pub fn next(&self) -> usize {
         self.0.next
     }
+
+    pub(crate) fn set_next(&mut self, new_next: usize) {
+        self.0.next = new_next;
+    }
 }
 
-pub(crate) struct Used<T>(UsedInner<T>);
+/// A slot holding an item of a `Deque`. Like [`Slot`], this is part of
+/// the unstable raw layout and may change between versions.
+///
+/// [`Slot`]: enum.Slot.html
+#[derive(Clone)]
+pub struct Used<T>(UsedInner<T>);
+#[derive(Clone)]
 struct UsedInner<T> {
     // The index of the slot before this slot.
     front: usize,
@@ -36,7 +54,9 @@ impl<T> Used<T> {
         })
     }
 
-    pub(crate) fn front(&self) -> usize {
+    /// The index of the slot in front of this one. MAX at the front
+    /// of the deque.
+    pub fn front(&self) -> usize {
         self.0.front
     }
 
@@ -44,7 +64,9 @@ impl<T> Used<T> {
         self.0.front = new_front;
     }
 
-    pub(crate) fn back(&self) -> usize {
+    /// The index of the slot behind this one. MAX at the back of the
+    /// deque.
+    pub fn back(&self) -> usize {
         self.0.back
     }
 
@@ -52,6 +74,16 @@ impl<T> Used<T> {
         self.0.back = new_back;
     }
 
+    /// The generation of the item, which tokens must match.
+    pub fn generation(&self) -> usize {
+        self.0.generation
+    }
+
+    /// Consume the slot and return the item held in it.
+    pub fn into_data(self) -> T {
+        self.0.data
+    }
+
     pub(crate) fn take(self) -> (usize, T, usize) {
         let Used(UsedInner {
             front, back, data, ..
@@ -75,7 +107,8 @@ impl<T> Used<T> {
         }
     }
 
-    pub(crate) fn data(&self) -> &T {
+    /// The item held in this slot.
+    pub fn data(&self) -> &T {
         &self.0.data
     }
 
@@ -84,21 +117,36 @@ impl<T> Used<T> {
     }
 }
 
-pub(crate) enum Slot<T> {
+/// One element of the `Vec` that backs a `Deque`. It is exposed
+/// through [`DequeParts`] for callers that need to move the raw
+/// storage of a deque in and out.
+///
+/// The slot representation, and the constructors and accessors on
+/// `Slot`, `Free`, and `Used`, are an unstable raw layout. They may
+/// change between versions as the deque's internals change.
+///
+/// [`DequeParts`]: struct.DequeParts.html
+#[derive(Clone)]
+pub enum Slot<T> {
+    /// A slot on the free list.
     Free(Free),
+    /// A slot holding an item.
     Used(Used<T>),
 }
 
 impl<T> Slot<T> {
-    pub(crate) fn new_free(next: usize) -> Slot<T> {
+    /// Create a free slot that links to `next` on the free list.
+    pub fn new_free(next: usize) -> Slot<T> {
         Slot::Free(Free::new(next))
     }
 
-    pub(crate) fn new_used(front: usize, back: usize, generation: usize, data: T) -> Slot<T> {
+    /// Create a used slot holding `data`, linked to `front` and `back`.
+    pub fn new_used(front: usize, back: usize, generation: usize, data: T) -> Slot<T> {
         Slot::Used(Used::new(front, back, generation, data))
     }
 
-    pub(crate) fn get_used(&self) -> Option<&Used<T>> {
+    /// Get the used slot, or `None` if the slot is free.
+    pub fn get_used(&self) -> Option<&Used<T>> {
         if let Slot::Used(used) = self {
             Some(used)
         } else {
@@ -114,7 +162,16 @@ impl<T> Slot<T> {
         }
     }
 
-    pub(crate) fn get_free(&self) -> Option<&Free> {
+    /// Get the free slot, or `None` if the slot is used.
+    pub fn get_free(&self) -> Option<&Free> {
+        if let Slot::Free(free) = self {
+            Some(free)
+        } else {
+            None
+        }
+    }
+
+    pub(crate) fn get_free_mut(&mut self) -> Option<&mut Free> {
         if let Slot::Free(free) = self {
             Some(free)
         } else {
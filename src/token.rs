@@ -1,3 +1,6 @@
+use crate::deque::Deque;
+use std::collections::HashMap;
+
 /// A token representing an item in the `List`. It can be used to try
 /// and remove the item from the list, or try to get the value of the
 /// item in the list. It contains a generation number that prevents
@@ -6,13 +9,106 @@
 ///
 /// Tokens can be stored in other data structures, and do not have
 /// lifetime bindings to the list that created them. Furthermore, they
-/// can safely be serialized as they do not contain pointers.
+/// can safely be serialized as they do not contain pointers. For the
+/// same reason, they are cheap to copy.
 ///
 /// While the type system allows it, using a `Token` with a list other
 /// than the one that created it will result in (likely) unexpected
 /// behavior.
-#[derive(Debug, Clone, PartialEq, Eq)]
+///
+/// Tokens are ordered by their location and then by their generation.
+/// This order is only meant to allow tokens to be used as keys in
+/// ordered collections; it has nothing to do with the position of the
+/// items in the list.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct Token {
     pub(crate) ix: usize,
     pub(crate) generation: usize,
 }
+
+impl Token {
+    /// Look up the token in `remap`, such as the map returned by
+    /// [`clone_compact`], and return the token that replaces it. If the
+    /// token has no entry, because its item did not survive, `None` is
+    /// returned.
+    ///
+    /// This is the same as [`Deque::remap_token`], but doesn't need the
+    /// deque's item type to be named.
+    ///
+    /// [`clone_compact`]: struct.Deque.html#method.clone_compact
+    /// [`Deque::remap_token`]: struct.Deque.html#method.remap_token
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use token_deque::Deque;
+    ///
+    /// let mut d = Deque::new();
+    /// let a = d.push_back(1);
+    /// let b = d.push_back(2);
+    /// d.remove(&a);
+    ///
+    /// let (compact, remap) = d.clone_compact();
+    /// assert_eq!(None, a.remap(&remap));
+    /// assert_eq!(Some(&2), compact.get(&b.remap(&remap).unwrap()));
+    /// ```
+    pub fn remap(&self, remap: &HashMap<Token, Token>) -> Option<Token> {
+        Deque::<()>::remap_token(remap, self)
+    }
+
+    /// Replace each of `tokens` with the token it maps to in `remap`,
+    /// in place. Tokens with no entry in `remap` are dropped, and the
+    /// rest keep their order.
+    ///
+    /// This is [`Deque::remap_tokens_in_place`] for a `Vec`, which is
+    /// truncated to the surviving tokens. Like [`remap`], it doesn't
+    /// need the deque's item type to be named.
+    ///
+    /// [`remap`]: #method.remap
+    /// [`Deque::remap_tokens_in_place`]: struct.Deque.html#method.remap_tokens_in_place
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use token_deque::{Deque, Token};
+    ///
+    /// let mut d = Deque::new();
+    /// let mut toks = vec![d.push_back(1), d.push_back(2), d.push_back(3)];
+    /// d.remove(&toks[1]);
+    ///
+    /// let (compact, remap) = d.clone_compact();
+    /// Token::remap_in_place(&remap, &mut toks);
+    /// assert_eq!(2, toks.len());
+    /// assert_eq!(Some(&3), compact.get(&toks[1]));
+    /// ```
+    pub fn remap_in_place(remap: &HashMap<Token, Token>, tokens: &mut Vec<Token>) {
+        let kept = Deque::<()>::remap_tokens_in_place(remap, tokens);
+        tokens.truncate(kept);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn remapped_tokens_resolve_after_compaction() {
+        let mut l = Deque::new();
+        let mut toks: Vec<Token> = (1..=6).map(|i| l.push_back(i)).collect();
+        l.remove(&toks[0]);
+        l.remove(&toks[3]);
+
+        let (compact, remap) = l.clone_compact();
+        assert_eq!(None, Deque::<u8>::remap_token(&remap, &toks[3]));
+        assert_eq!(
+            Some(remap[&toks[1]]),
+            Deque::<u8>::remap_token(&remap, &toks[1])
+        );
+
+        let kept = Deque::<u8>::remap_tokens_in_place(&remap, &mut toks);
+        assert_eq!(4, kept);
+        toks.truncate(kept);
+        let values: Vec<&u8> = toks.iter().map(|t| compact.get(t).unwrap()).collect();
+        assert_eq!(vec![&2, &3, &5, &6], values);
+    }
+}
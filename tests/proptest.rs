@@ -14,7 +14,7 @@ proptest! {
 
         let len = pushes.len();
 
-        for (p,v) in pushes.into_iter().zip((0..len).into_iter()) {
+        for (p,v) in pushes.into_iter().zip(0..len) {
             if p {
                 l.push_front(v);
             } else {
@@ -82,3 +82,42 @@ proptest! {
         assert!(list.is_empty());
     }
 }
+
+proptest! {
+    #[test]
+    fn random_operations_stay_valid(
+        seed in any::<u64>(),
+        action in proptest::collection::vec(any::<usize>(), 0..128)
+    ){
+        let mut rng = StdRng::seed_from_u64(seed);
+        let mut l: Deque<usize> = Deque::new();
+        let mut tokens = Vec::new();
+
+        for a in action {
+            match a % 7 {
+                0 => tokens.push(l.push_front(a)),
+                1 => tokens.push(l.push_back(a)),
+                2 => {
+                    l.pop_front();
+                },
+                3 => {
+                    l.pop_back();
+                },
+                4 => {
+                    if let Some(t) = tokens.choose(&mut rng) {
+                        l.remove(t);
+                    }
+                },
+                5 => {
+                    l.drain_front().take(a % 3).for_each(drop);
+                },
+                6 => {
+                    l.drain_back().take(a % 3).for_each(drop);
+                },
+                _ => unreachable!(),
+            }
+
+            prop_assert_eq!(Ok(()), l.validate());
+        }
+    }
+}